@@ -0,0 +1,196 @@
+//! Solve a problem in MPS, CPLEX LP or JSON ( with `json` feature ) format:
+//! ```text
+//! simplex [OPTIONS] [FILE]
+//! ```
+//! and print the status, the optimal value, the variables and the duals.
+//! Reads stdin when `FILE` is omitted or `-`.
+
+#[cfg(test)] mod test;
+
+use std::{env, fs, process::ExitCode, time::Duration};
+use std::io::{self, Read, Write};
+use simplex_method::{Algorithm, PivotRule, Problem, SolverOptions};
+
+
+const USAGE: &str = "\
+Usage: simplex [OPTIONS] [FILE]
+
+Solve a linear programming problem read from FILE, or stdin when FILE is omitted or `-`.
+
+Options:
+  --format <mps|lp|json>                     Input format. By default, guessed from the extension or the content
+  --algorithm <primal|dual|revised>          Simplex method to use [default: primal]
+  --pivot-rule <dantzig|bland|lexicographic> Pivot rule [default: dantzig]
+  --max-iterations <N>                       Maximum number of pivot operations
+  --time-limit <SECONDS>                     Wall-clock limit of the whole solving
+  --max-nodes <N>                            Maximum number of LP relaxations in branch-and-bound
+  --gap-tolerance <X>                        Relative MIP gap to stop branch-and-bound [default: 1e-6]
+  --cut-rounds <N>                           Gomory cuts at the root of a pure integer problem [default: 0]
+  --trace                                    Print each table of two-phase primal simplex method on the LP relaxation to stderr
+  -h, --help                                 Print this help
+";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format { Mps, Lp, Json }
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    file:    Option<String>,
+    format:  Option<Format>,
+    trace:   bool,
+    options: SolverOptions,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    fn value<T: std::str::FromStr>(option: &str, value: Option<String>) -> Result<T, String> {
+        let value = value.ok_or_else(|| format!("`{option}` needs a value"))?;
+        value.parse().map_err(|_| format!("Invalid value for `{option}`: `{value}`"))
+    }
+
+    let mut parsed = Args { file: None, format: None, trace: false, options: SolverOptions::default() };
+    let options = &mut parsed.options;
+    while let Some(arg) = args.next() {
+        match &*arg {
+            "-h" | "--help"    => return Ok(None),
+            "--trace"          => parsed.trace = true,
+            "--max-iterations" => options.max_iterations = Some(value(&arg, args.next())?),
+            "--max-nodes"      => options.max_nodes      = Some(value(&arg, args.next())?),
+            "--cut-rounds"     => options.cut_rounds     = value(&arg, args.next())?,
+            "--gap-tolerance"  => options.gap_tolerance  = value(&arg, args.next())?,
+            "--time-limit"     => {
+                /* Negative, infinite or NaN seconds are not a `Duration` */
+                let seconds = value::<String>(&arg, args.next())?;
+                options.time_limit = Some(seconds.parse().ok()
+                    .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+                    .ok_or_else(|| format!("Invalid value for `{arg}`: `{seconds}`"))?);
+            }
+            "--format" => parsed.format = Some(match &*value::<String>(&arg, args.next())? {
+                "mps"  => Format::Mps,
+                "lp"   => Format::Lp,
+                "json" => Format::Json,
+                other  => return Err(format!("Unknown format `{other}`")),
+            }),
+            "--algorithm" => options.algorithm = match &*value::<String>(&arg, args.next())? {
+                "primal"  => Algorithm::Primal,
+                "dual"    => Algorithm::Dual,
+                "revised" => Algorithm::Revised,
+                other     => return Err(format!("Unknown algorithm `{other}`")),
+            },
+            "--pivot-rule" => options.pivot_rule = match &*value::<String>(&arg, args.next())? {
+                "dantzig"       => PivotRule::Dantzig,
+                "bland"         => PivotRule::Bland,
+                "lexicographic" => PivotRule::Lexicographic,
+                other           => return Err(format!("Unknown pivot rule `{other}`")),
+            },
+            option if option.starts_with('-') && option != "-" => return Err(format!("Unknown option `{option}`")),
+            file => {
+                if parsed.file.is_some() {return Err(format!("Unexpected argument `{file}`"))}
+                parsed.file = Some(file.to_string())
+            }
+        }
+    }
+    Ok(Some(parsed))
+}
+
+/// By the extension of `file`, or the content: JSON starts with `{` and MPS has a `ROWS` section
+fn guess_format(file: Option<&str>, input: &str) -> Format {
+    match file.and_then(|file| file.rsplit_once('.')).map(|(_, extension)| extension.to_ascii_lowercase()).as_deref() {
+        Some("mps")  => Format::Mps,
+        Some("lp")   => Format::Lp,
+        Some("json") => Format::Json,
+        _ if input.trim_start().starts_with('{') => Format::Json,
+        _ if input.lines().any(|line| line.trim_end() == "ROWS") => Format::Mps,
+        _ => Format::Lp,
+    }
+}
+
+fn read_problem(format: Format, input: &str) -> Result<Problem, String> {
+    match format {
+        Format::Mps  => Problem::from_mps(input.as_bytes()).map_err(|error| error.to_string()),
+        Format::Lp   => Problem::from_lp(input.as_bytes()).map_err(|error| error.to_string()),
+        #[cfg(feature = "json")]
+        Format::Json => serde_json::from_str(input).map_err(|error| error.to_string()),
+        #[cfg(not(feature = "json"))]
+        Format::Json => Err("JSON input needs `json` feature".to_string()),
+    }
+}
+
+/// Print each table of `Problem::steps`, with its `Debug`, to `err`
+fn trace(problem: Problem, options: SolverOptions, err: &mut impl Write) -> io::Result<()> {
+    let Ok(mut steps) = problem.steps(options) else {return Ok(())};
+    loop {
+        writeln!(err, "{:?}", steps.table())?;
+        match steps.next() {
+            Some(Ok(_)) => continue,
+            _           => return Ok(()),
+        }
+    }
+}
+
+/// Run with the arguments `args` except the program name, and return the exit code:
+/// 0 when solved to optimality or on `--help`, 1 when not solved, 2 on invalid arguments or input
+fn run(args: impl Iterator<Item = String>, stdin: impl Read, out: &mut impl Write, err: &mut impl Write) -> io::Result<u8> {
+    let args = match parse_args(args) {
+        Ok(Some(args)) => args,
+        Ok(None)       => {write!(out, "{USAGE}")?; return Ok(0)}
+        Err(message)   => {writeln!(err, "{message}\n\n{USAGE}")?; return Ok(2)}
+    };
+
+    let input = match args.file.as_deref() {
+        None | Some("-") => {
+            let mut input = String::new();
+            {stdin}.read_to_string(&mut input).map(|_| input)
+        }
+        Some(file) => fs::read_to_string(file),
+    };
+    let input = match input {
+        Ok(input)  => input,
+        Err(error) => {writeln!(err, "Failed to read: {error}")?; return Ok(2)}
+    };
+
+    let format = args.format.unwrap_or_else(|| guess_format(args.file.as_deref(), &input));
+    let problem = match read_problem(format, &input) {
+        Ok(problem)  => problem,
+        Err(message) => {writeln!(err, "{message}")?; return Ok(2)}
+    };
+    if let Err(error) = problem.validate() {
+        writeln!(err, "Invalid problem: {error}")?;
+        return Ok(2)
+    }
+
+    if args.trace {
+        trace(problem.clone(), args.options, err)?;
+    }
+
+    let variables = problem.variables().to_vec();
+    let names = problem.constraint_names().to_vec();
+    let solution = match problem.solve_with(args.options) {
+        Ok(solution) => solution,
+        Err(error)   => {writeln!(out, "Status: {error}")?; return Ok(1)}
+    };
+
+    writeln!(out, "Status: Optimal")?;
+    writeln!(out, "Objective value: {}", solution.optimal_value)?;
+    if let Some(gap) = solution.mip_gap {
+        writeln!(out, "MIP gap: {gap}")?;
+    }
+    writeln!(out, "Variables:")?;
+    for variable in &variables {
+        writeln!(out, "  {variable:?} = {}", solution.variables[variable])?;
+    }
+    writeln!(out, "Duals:")?;
+    for (i, dual) in solution.duals.iter().enumerate() {
+        match names.get(i) {
+            Some(name) => writeln!(out, "  {name} = {dual}")?,
+            None       => writeln!(out, "  R{} = {dual}", i + 1)?,
+        }
+    }
+    Ok(0)
+}
+
+fn main() -> ExitCode {
+    match run(env::args().skip(1), io::stdin(), &mut io::stdout(), &mut io::stderr()) {
+        Ok(code) => ExitCode::from(code),
+        Err(_)   => ExitCode::from(2),
+    }
+}
@@ -0,0 +1,167 @@
+use std::time::Duration;
+use simplex_method::{Algorithm, PivotRule, SolverOptions};
+use super::{parse_args, guess_format, run, Args, Format, USAGE};
+
+
+fn args<'a>(args: &'a [&str]) -> impl Iterator<Item = String> + 'a {
+    args.iter().map(|arg| arg.to_string())
+}
+
+/// The exit code, stdout and stderr of `run` with `args` and `stdin`
+fn run_with(arguments: &[&str], stdin: &str) -> (u8, String, String) {
+    let (mut out, mut err) = (Vec::new(), Vec::new());
+    let code = run(args(arguments), stdin.as_bytes(), &mut out, &mut err).unwrap();
+    (code, String::from_utf8(out).unwrap(), String::from_utf8(err).unwrap())
+}
+
+const LP: &str = "\
+Maximize
+ obj: 3 x1 + 2 x2
+Subject To
+ lim1: x1 + x2 <= 4
+ lim2: x1 + 3 x2 <= 6
+End
+";
+
+
+#[test] fn test_parse_args() {
+    assert_eq!(parse_args(args(&[])), Ok(Some(Args {
+        file:    None,
+        format:  None,
+        trace:   false,
+        options: SolverOptions::default(),
+    })));
+
+    assert_eq!(parse_args(args(&[
+        "--format", "mps",
+        "--algorithm", "revised",
+        "--pivot-rule", "lexicographic",
+        "--max-iterations", "100",
+        "--time-limit", "1.5",
+        "--max-nodes", "20",
+        "--gap-tolerance", "0.01",
+        "--cut-rounds", "3",
+        "--trace",
+        "problem.txt",
+    ])), Ok(Some(Args {
+        file:    Some("problem.txt".to_string()),
+        format:  Some(Format::Mps),
+        trace:   true,
+        options: SolverOptions {
+            algorithm:      Algorithm::Revised,
+            pivot_rule:     PivotRule::Lexicographic,
+            max_iterations: Some(100),
+            time_limit:     Some(Duration::from_millis(1500)),
+            max_nodes:      Some(20),
+            gap_tolerance:  0.01,
+            cut_rounds:     3,
+            ..SolverOptions::default()
+        },
+    })));
+
+    /* `-` is stdin, not an option */
+    assert_eq!(parse_args(args(&["-"])).unwrap().unwrap().file.as_deref(), Some("-"));
+
+    assert_eq!(parse_args(args(&["--help"])), Ok(None));
+    assert_eq!(parse_args(args(&["problem.lp", "-h"])), Ok(None));
+
+    for (arguments, message) in [
+        (&["--max-iterations"][..],            "`--max-iterations` needs a value"),
+        (&["--max-iterations", "many"],        "Invalid value for `--max-iterations`: `many`"),
+        (&["--time-limit", "-"],               "Invalid value for `--time-limit`: `-`"),
+        (&["--time-limit", "-1"],              "Invalid value for `--time-limit`: `-1`"),
+        (&["--time-limit", "inf"],             "Invalid value for `--time-limit`: `inf`"),
+        (&["--time-limit", "nan"],             "Invalid value for `--time-limit`: `nan`"),
+        (&["--format", "csv"],                 "Unknown format `csv`"),
+        (&["--algorithm", "interior"],         "Unknown algorithm `interior`"),
+        (&["--pivot-rule", "steepest"],        "Unknown pivot rule `steepest`"),
+        (&["--verbose"],                       "Unknown option `--verbose`"),
+        (&["a.lp", "b.lp"],                    "Unexpected argument `b.lp`"),
+    ] {
+        assert_eq!(parse_args(args(arguments)), Err(message.to_string()));
+    }
+}
+
+#[test] fn test_guess_format() {
+    /* By the extension */
+    assert_eq!(guess_format(Some("problem.mps"), ""), Format::Mps);
+    assert_eq!(guess_format(Some("problem.LP"), "ROWS"), Format::Lp);
+    assert_eq!(guess_format(Some("dir.v2/problem.json"), ""), Format::Json);
+
+    /* By the content */
+    assert_eq!(guess_format(None, "  {\"sense\": \"Maximize\"}"), Format::Json);
+    assert_eq!(guess_format(Some("problem"), "NAME test\nROWS\n N obj\n"), Format::Mps);
+    assert_eq!(guess_format(Some("problem.txt"), "NAME test\nROWS  \n"), Format::Mps);
+    assert_eq!(guess_format(None, LP), Format::Lp);
+    assert_eq!(guess_format(None, ""), Format::Lp);
+}
+
+#[test] fn test_run() {
+    let (code, out, err) = run_with(&[], LP);
+    assert_eq!(code, 0, "{err}");
+    assert_eq!(out, "\
+Status: Optimal
+Objective value: 12
+Variables:
+  x1 = 4
+  x2 = 0
+Duals:
+  lim1 = 3
+  lim2 = 0
+");
+    assert_eq!(err, "");
+
+    /* Tables of the steps to stderr, and the same result */
+    let (code, traced, err) = run_with(&["--trace", "-"], LP);
+    assert_eq!((code, traced), (0, out));
+    assert_eq!(err.matches("slack#1").count(), 3, "{err}");
+
+    let (code, out, err) = run_with(&["--help"], "");
+    assert_eq!((code, &*out, &*err), (0, USAGE, ""));
+}
+
+#[test] fn test_run_errors() {
+    /* Invalid arguments */
+    let (code, out, err) = run_with(&["--format"], LP);
+    assert_eq!((code, &*out), (2, ""));
+    assert!(err.starts_with("`--format` needs a value\n\nUsage: "), "{err}");
+
+    /* Unreadable file */
+    let (code, out, err) = run_with(&["no/such/problem.lp"], "");
+    assert_eq!((code, &*out), (2, ""));
+    assert!(err.starts_with("Failed to read: "), "{err}");
+
+    /* Invalid input */
+    let (code, out, err) = run_with(&["--format", "mps"], LP);
+    assert_eq!((code, &*out), (2, ""));
+    assert!(!err.is_empty());
+
+    #[cfg(not(feature = "json"))] {
+        let (code, out, err) = run_with(&["--format", "json"], "{}");
+        assert_eq!((code, &*out, &*err), (2, "", "JSON input needs `json` feature\n"));
+    }
+
+    /* A column the solver would make itself */
+    #[cfg(feature = "json")] {
+        use simplex_method::{var, Constraint, Problem, Variable};
+        let problem = Problem::maximize(1*var("x"), [
+            Constraint::le(1*var("x") + 1*Variable::Slack { id: 1 }, 4),
+        ].into_iter().collect());
+        let (code, out, err) = run_with(&["--format", "json"], &serde_json::to_string(&problem).unwrap());
+        assert_eq!((code, &*out, &*err), (2, "", "Invalid problem: `slack#1` is a column of the condition but not a normal variable\n"));
+    }
+
+    /* Not solved */
+    let (code, out, err) = run_with(&[], "\
+Maximize
+ obj: x1
+Subject To
+ c1: x1 <= 1
+ c2: x1 >= 2
+End
+");
+    assert_eq!((code, &*out, &*err), (1, "Status: Infeasible: no solution satisfies all the constraints\n", ""));
+
+    let (code, out, _) = run_with(&["--max-iterations", "0"], LP);
+    assert_eq!((code, &*out), (1, "Status: Reached iteration limit: 0 pivot operations\n"));
+}
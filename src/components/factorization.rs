@@ -0,0 +1,162 @@
+use super::scalor::Scalor;
+
+
+/// Sparse LU factorization `P B Q = L U` of a square matrix `B`, followed by the eta matrices
+/// of product-form updates: after the updates `E_1, ..., E_k`, this represents `B⁻¹ = E_k ... E_1 (L U)⁻¹` up to the permutations.
+///
+/// Factorizing is left-looking: the columns of `B` are taken sparsest first ( `Q` ), each is transformed by `L⁻¹` so far,
+/// and its entry of the largest absolute value among the rows not yet pivoted is the pivot ( `P` ).
+/// The entries on the pivoted rows make a column of `U`, and the others divided by the pivot a column of `L`.
+pub(crate) struct Factorization {
+    /// Columns of `L` below the diagonal ( whose diagonal is all 1 ), in the order of pivoting. Empty ones are omitted.
+    lower: Vec<Lower>,
+    /// Columns of `U`, in the order of pivoting
+    upper: Vec<Upper>,
+    etas:  Vec<Eta>,
+}
+
+/// A column of `L`: `B`'s rows are reduced by `multipliers` times the pivot row `row`
+struct Lower {
+    row:         usize,
+    multipliers: Vec<(usize, Scalor)>,
+}
+
+/// A column of `U` whose pivot is at `row`: `above` are its entries on the rows pivoted before
+struct Upper {
+    row:   usize,
+    pivot: Scalor,
+    above: Vec<(usize, Scalor)>,
+}
+
+/// The identity matrix whose column `row` is replaced by the one made from `B⁻¹ a`,
+/// when `a` replaces the column `row` of `B`: `pivot` is its entry at `row`, and `others` the other nonzero ones
+struct Eta {
+    row:    usize,
+    pivot:  Scalor,
+    others: Vec<(usize, Scalor)>,
+}
+
+impl Factorization {
+    /// Factorize the matrix whose columns are `columns`, sparse ones of `(row, value)`.
+    /// Returns the factorization with the pivot row of each column: the factorized matrix `B` has `columns[k]`
+    /// as its column `rows[k]`, so that `ftran` gives the value of `columns[k]` at `rows[k]`.
+    /// `None` when it's singular: some pivot's absolute value is `<= tolerance`.
+    pub(crate) fn new(columns: &[Vec<(usize, Scalor)>], tolerance: Scalor) -> Option<(Self, Vec<usize>)> {
+        let size = columns.len();
+        let mut order = (0..size).collect::<Vec<_>>();
+        order.sort_by_key(|&k| columns[k].len());
+
+        let (mut lower, mut upper) = (Vec::<Lower>::new(), Vec::with_capacity(size));
+        let mut rows = vec![0; size];
+        let mut pivoted = vec![false; size];
+        let mut work = vec![0.; size];
+        for k in order {
+            /* `work` is `L⁻¹ a` on the rows in `touched` and 0 on the others */
+            let mut touched = Vec::with_capacity(columns[k].len());
+            for &(i, a) in &columns[k] {
+                work[i] = a;
+                touched.push(i);
+            }
+            for Lower { row, multipliers } in &lower {
+                if work[*row] == 0. {continue}
+                for &(i, l) in multipliers {
+                    if work[i] == 0. {touched.push(i)}
+                    work[i] -= l * work[*row];
+                }
+            }
+            touched.sort_unstable();
+            touched.dedup();
+
+            let row = touched.iter().copied()
+                .filter(|&i| !pivoted[i])
+                .max_by(|&i, &j| work[i].abs().total_cmp(&work[j].abs()))
+                .filter(|&i| work[i].abs() > tolerance)?;
+            let pivot = work[row];
+
+            let (mut above, mut multipliers) = (Vec::new(), Vec::new());
+            for &i in touched.iter().filter(|&&i| i != row && work[i] != 0.) {
+                if pivoted[i] {above.push((i, work[i]))} else {multipliers.push((i, work[i] / pivot))}
+            }
+            for i in touched {
+                work[i] = 0.
+            }
+
+            pivoted[row] = true;
+            rows[k] = row;
+            upper.push(Upper { row, pivot, above });
+            if !multipliers.is_empty() {
+                lower.push(Lower { row, multipliers });
+            }
+        }
+
+        Some((Self { lower, upper, etas: Vec::new() }, rows))
+    }
+
+    /// Number of product-form updates since factorized
+    pub(crate) fn updates(&self) -> usize {
+        self.etas.len()
+    }
+
+    /// `B⁻¹ v`
+    pub(crate) fn ftran(&self, v: &[Scalor]) -> Vec<Scalor> {
+        let mut x = v.to_vec();
+
+        /* L⁻¹ v, then U⁻¹ backward: each pivot row gets the value of the column pivoted there */
+        for Lower { row, multipliers } in &self.lower {
+            if x[*row] == 0. {continue}
+            for (i, l) in multipliers {
+                x[*i] -= l * x[*row];
+            }
+        }
+        for Upper { row, pivot, above } in self.upper.iter().rev() {
+            if x[*row] == 0. {continue}
+            x[*row] /= pivot;
+            for (i, u) in above {
+                x[*i] -= u * x[*row];
+            }
+        }
+
+        for Eta { row, pivot, others } in &self.etas {
+            if x[*row] == 0. {continue}
+            x[*row] /= pivot;
+            for (i, a) in others {
+                x[*i] -= a * x[*row];
+            }
+        }
+        x
+    }
+
+    /// `v B⁻¹`
+    pub(crate) fn btran(&self, v: &[Scalor]) -> Vec<Scalor> {
+        let mut v = v.to_vec();
+        for Eta { row, pivot, others } in self.etas.iter().rev() {
+            let others = others.iter()
+                .map(|(i, a)| v[*i] * a)
+                .sum::<Scalor>();
+            v[*row] = (v[*row] - others) / pivot;
+        }
+
+        /* z U = v forward, then z L⁻¹ backward */
+        for Upper { row, pivot, above } in &self.upper {
+            let above = above.iter()
+                .map(|(i, u)| v[*i] * u)
+                .sum::<Scalor>();
+            v[*row] = (v[*row] - above) / pivot;
+        }
+        for Lower { row, multipliers } in self.lower.iter().rev() {
+            v[*row] -= multipliers.iter()
+                .map(|(i, l)| v[*i] * l)
+                .sum::<Scalor>();
+        }
+        v
+    }
+
+    /// Replace the column `row` of `B` by `a`, where `column = B⁻¹ a`
+    pub(crate) fn update(&mut self, row: usize, column: Vec<Scalor>) {
+        let others = column.iter().enumerate()
+            .filter(|&(i, a)| i != row && *a != 0.)
+            .map(|(i, a)| (i, *a))
+            .collect();
+        self.etas.push(Eta { row, pivot: column[row], others })
+    }
+}
@@ -1,7 +1,14 @@
 use std::ops::Mul;
 use super::{scalor::Scalor, variable::Variable, polynomial::Polynomial};
+use crate::error::MatrixError;
 
 
+/// With `serde` feature, serialized as its rows, and rows of different sizes fail to deserialize
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(
+    try_from = "Vec<Vec<T>>", into = "Vec<Vec<T>>",
+    bound(serialize = "T: Clone + serde::Serialize", deserialize = "T: serde::Deserialize<'de>"),
+))]
 pub struct Matrix<T> {
     pub rows:        Vec<Vec<T>>,
     pub row_size:    usize,
@@ -9,10 +16,10 @@ pub struct Matrix<T> {
 }
 
 impl<T> Matrix<T> {
-    pub fn column_iter(&self, index: usize) -> Result<impl Iterator<Item = &T>, String> {
+    pub fn column_iter(&self, index: usize) -> Result<impl Iterator<Item = &T>, MatrixError> {
         (index < self.row_size)
             .then(|| (0..self.column_size).map(move |i| &self.rows[i][index]))
-            .ok_or_else(|| format!("Index out of bound: row size is {} but given index is {}", self.row_size, index))
+            .ok_or(MatrixError::IndexOutOfBound { size: self.row_size, index })
     }
 }
 
@@ -25,18 +32,36 @@ impl<T> Matrix<T> {
         Self { rows, row_size, column_size }
     }
 
-    pub fn push_row(&mut self, row: Vec<T>) -> Result<(), String> {
+    pub fn push_row(&mut self, row: Vec<T>) -> Result<(), MatrixError> {
         let row_size = row.len();
         (self.row_size == row_size)
             .then(|| {self.rows.push(row); self.column_size += 1})
-            .ok_or_else(|| format!("Row size is {} but new row's size is {}", self.row_size, row_size))
+            .ok_or(MatrixError::RowSizeMismatch { expected: self.row_size, found: row_size })
+    }
+
+    /// Panics if `index > column_size`, as `Vec::insert`
+    pub fn insert_row(&mut self, index: usize, row: Vec<T>) -> Result<(), MatrixError> {
+        let row_size = row.len();
+        (self.row_size == row_size)
+            .then(|| {self.rows.insert(index, row); self.column_size += 1})
+            .ok_or(MatrixError::RowSizeMismatch { expected: self.row_size, found: row_size })
+    }
+
+    pub fn push_column(&mut self, column: Vec<T>) -> Result<(), MatrixError> {
+        let column_size = column.len();
+        (self.column_size == column_size)
+            .then(|| {
+                for (row, item) in self.rows.iter_mut().zip(column) {row.push(item)}
+                self.row_size += 1
+            })
+            .ok_or(MatrixError::ColumnSizeMismatch { expected: self.column_size, found: column_size })
     }
 }
 
 impl<T: Clone> Matrix<T> {
-    pub fn try_concat(self, another: Self) -> Result<Self, String> {
+    pub fn try_concat(self, another: Self) -> Result<Self, MatrixError> {
         if self.column_size != another.column_size {
-            return Err((|| format!("Column sizes not match"))())
+            return Err(MatrixError::ColumnSizeMismatch { expected: self.column_size, found: another.column_size })
         }
 
         let row_size    = self.row_size + another.row_size;
@@ -51,12 +76,12 @@ impl<T: Clone> Matrix<T> {
 }
 
 impl Matrix<Scalor> {
-    pub fn try_from<Row: IntoIterator<Item = Scalor>>(rows: impl IntoIterator<Item = Row>) -> Result<Self, String> {
+    pub fn try_from<Row: IntoIterator<Item = Scalor>>(rows: impl IntoIterator<Item = Row>) -> Result<Self, MatrixError> {
         let mut rows = rows.into_iter();
 
         let first_row: Vec<Scalor> = match rows.next() {
             Some(r) => r.into_iter().collect(),
-            None    => return Err((|| format!("Got empty rows in input"))()),
+            None    => return Err(MatrixError::EmptyRows),
         };
 
         let row_size = first_row.len();
@@ -67,7 +92,7 @@ impl Matrix<Scalor> {
         for row in rows {
             let row = row.into_iter().collect::<Vec<_>>();
             if row.len() != row_size {
-                return Err((|| format!("Rows have different lengths"))())
+                return Err(MatrixError::RowSizeMismatch { expected: row_size, found: row.len() })
             }
             collected_rows.push(row);
             column_size += 1;
@@ -99,6 +124,22 @@ impl Matrix<Scalor> {
 
 
 const _: () = {
+    impl<T> TryFrom<Vec<Vec<T>>> for Matrix<T> {
+        type Error = MatrixError;
+        fn try_from(rows: Vec<Vec<T>>) -> Result<Self, Self::Error> {
+            let row_size = rows.first().ok_or(MatrixError::EmptyRows)?.len();
+            if let Some(row) = rows.iter().find(|row| row.len() != row_size) {
+                return Err(MatrixError::RowSizeMismatch { expected: row_size, found: row.len() })
+            }
+            Ok(Self { column_size: rows.len(), rows, row_size })
+        }
+    }
+    impl<T> From<Matrix<T>> for Vec<Vec<T>> {
+        fn from(matrix: Matrix<T>) -> Self {
+            matrix.rows
+        }
+    }
+
     impl<T: std::fmt::Debug> std::fmt::Debug for Matrix<T> {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             f.write_str(&format!("{:?}", self.rows))
@@ -111,7 +152,7 @@ const _: () = {
             if other.column_size != column_size {return false}
             if other.row_size != row_size {return false}
             for i in 0..column_size {
-                if &self.rows[i] != &other.rows[i] {return false}
+                if self.rows[i] != other.rows[i] {return false}
             }
 
             true
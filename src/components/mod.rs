@@ -3,3 +3,6 @@ pub mod variable;
 pub mod term;
 pub mod polynomial;
 pub mod matrix;
+pub mod sparse;
+pub(crate) mod factorization;
+pub mod range;
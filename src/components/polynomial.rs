@@ -1,60 +1,216 @@
-use std::ops::{Add, AddAssign, Sub, SubAssign};
-use super::term::Term;
+use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, Neg};
+use std::iter::Sum;
+use super::{scalor::Scalor, term::Term};
 
 
-#[derive(Debug, PartialEq)]
+/// Sum of terms and a constant.
+/// 
+/// Terms are kept canonical: each variable appears at most once,
+/// and terms whose coefficients get 0 are removed.
+/// 
+/// ```
+/// use simplex_method::{var, Polynomial, Term};
+/// 
+/// let x = (1..=3).map(|i| var(format!("x{i}"))).collect::<Vec<_>>();
+/// let total = x.iter().map(|x| 2*x).sum::<Polynomial>();
+/// assert_eq!(total.terms[0], Term { coefficient: 2., variable: var("x1") });
+/// assert_eq!(total.terms.len(), 3);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(from = "Terms"))]
 pub struct Polynomial {
-    pub terms: Vec<Term>,
+    pub terms:    Vec<Term>,
+    pub constant: Scalor,
 }
 
 impl Polynomial {
     pub fn new() -> Self {
-        Self { terms: Vec::new() }
+        Self { terms: Vec::new(), constant: 0. }
+    }
+}
+impl Default for Polynomial {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Deserialized `Polynomial` before its terms are made canonical
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct Terms {
+    terms:    Vec<Term>,
+    constant: Scalor,
+}
+#[cfg(feature = "serde")]
+impl From<Terms> for Polynomial {
+    fn from(Terms { terms, constant }: Terms) -> Self {
+        terms.into_iter().sum::<Polynomial>() + constant
     }
 }
 
 const _: () = {
     impl From<Term> for Polynomial {
         fn from(term: Term) -> Self {
-            Self { terms: vec![term] }
+            Polynomial::new() + term
         }
     }
 
-    impl Add<Term> for Term {
-        type Output = Polynomial;
-        fn add(self, another: Term) -> Self::Output {
-            Polynomial { terms: vec![self] } + another
+    impl AddAssign<Term> for Polynomial {
+        fn add_assign(&mut self, Term { coefficient, variable }: Term) {
+            match self.terms.iter().position(|term| term.variable == variable) {
+                Some(i) => {
+                    self.terms[i].coefficient += coefficient;
+                    if self.terms[i].coefficient == 0. {self.terms.remove(i);}
+                }
+                None => if coefficient != 0. {
+                    self.terms.push(Term { coefficient, variable })
+                }
+            }
+        }
+    }
+    impl SubAssign<Term> for Polynomial {
+        fn sub_assign(&mut self, term: Term) {
+            *self += -term
         }
     }
     impl Add<Term> for Polynomial {
         type Output = Polynomial;
         fn add(mut self, new_term: Term) -> Self::Output {
-            self.terms.push(new_term);
+            self += new_term;
             self
         }
     }
-    impl AddAssign<Term> for Polynomial {
-        fn add_assign(&mut self, new_term: Term) {
-            self.terms.push(new_term);
+    impl Sub<Term> for Polynomial {
+        type Output = Polynomial;
+        fn sub(mut self, term: Term) -> Self::Output {
+            self -= term;
+            self
+        }
+    }
+    impl Add<Term> for Term {
+        type Output = Polynomial;
+        fn add(self, another: Term) -> Self::Output {
+            Polynomial::from(self) + another
         }
     }
-
     impl Sub<Term> for Term {
         type Output = Polynomial;
         fn sub(self, another: Term) -> Self::Output {
-            Polynomial { terms: vec![self] } - another
+            Polynomial::from(self) - another
         }
     }
-    impl Sub<Term> for Polynomial {
+
+    impl AddAssign<Polynomial> for Polynomial {
+        fn add_assign(&mut self, another: Polynomial) {
+            for term in another.terms {*self += term}
+            self.constant += another.constant;
+        }
+    }
+    impl SubAssign<Polynomial> for Polynomial {
+        fn sub_assign(&mut self, another: Polynomial) {
+            *self += -another
+        }
+    }
+    impl Add<Polynomial> for Polynomial {
         type Output = Polynomial;
-        fn sub(mut self, Term { coefficient, variable }: Term) -> Self::Output {
-            self.terms.push(Term { coefficient:-coefficient, variable });
+        fn add(mut self, another: Polynomial) -> Self::Output {
+            self += another;
             self
         }
     }
-    impl SubAssign<Term> for Polynomial {
-        fn sub_assign(&mut self, Term { coefficient, variable }: Term) {
-            self.terms.push(Term { coefficient:-coefficient, variable });
+    impl Sub<Polynomial> for Polynomial {
+        type Output = Polynomial;
+        fn sub(mut self, another: Polynomial) -> Self::Output {
+            self -= another;
+            self
+        }
+    }
+    impl Add<Polynomial> for Term {
+        type Output = Polynomial;
+        fn add(self, another: Polynomial) -> Self::Output {
+            Polynomial::from(self) + another
+        }
+    }
+    impl Sub<Polynomial> for Term {
+        type Output = Polynomial;
+        fn sub(self, another: Polynomial) -> Self::Output {
+            Polynomial::from(self) - another
+        }
+    }
+
+    impl Neg for Term {
+        type Output = Term;
+        fn neg(self) -> Self::Output {
+            Term { coefficient: -self.coefficient, variable: self.variable }
         }
     }
+    impl Neg for Polynomial {
+        type Output = Polynomial;
+        fn neg(self) -> Self::Output {
+            self * -1.
+        }
+    }
+
+    impl Sum<Term> for Polynomial {
+        fn sum<I: Iterator<Item = Term>>(terms: I) -> Self {
+            terms.fold(Polynomial::new(), |p, term| p + term)
+        }
+    }
+    impl Sum<Polynomial> for Polynomial {
+        fn sum<I: Iterator<Item = Polynomial>>(polynomials: I) -> Self {
+            polynomials.fold(Polynomial::new(), |p, another| p + another)
+        }
+    }
+
+    macro_rules! with_scalor {
+        ($( $s:ty )*) => {$(
+            impl Add<$s> for Polynomial {
+                type Output = Polynomial;
+                fn add(mut self, constant: $s) -> Self::Output {
+                    self.constant += Scalor::from(constant);
+                    self
+                }
+            }
+            impl Sub<$s> for Polynomial {
+                type Output = Polynomial;
+                fn sub(mut self, constant: $s) -> Self::Output {
+                    self.constant -= Scalor::from(constant);
+                    self
+                }
+            }
+            impl Add<$s> for Term {
+                type Output = Polynomial;
+                fn add(self, constant: $s) -> Self::Output {
+                    Polynomial::from(self) + constant
+                }
+            }
+            impl Sub<$s> for Term {
+                type Output = Polynomial;
+                fn sub(self, constant: $s) -> Self::Output {
+                    Polynomial::from(self) - constant
+                }
+            }
+
+            impl Mul<$s> for Polynomial {
+                type Output = Polynomial;
+                fn mul(self, scalor: $s) -> Self::Output {
+                    let scalor = Scalor::from(scalor);
+                    let Polynomial { terms, constant } = self;
+                    terms.into_iter()
+                        .map(|Term { coefficient, variable }| Term { coefficient: coefficient * scalor, variable })
+                        .sum::<Polynomial>() + constant * scalor
+                }
+            }
+            impl Mul<Polynomial> for $s {
+                type Output = Polynomial;
+                fn mul(self, polynomial: Polynomial) -> Self::Output {
+                    polynomial * self
+                }
+            }
+        )*};
+    } with_scalor! {
+        i8 i16 i32 /*i64 i128 */
+        u8 u16 u32 /*u64 u128 */
+        f32 f64
+    }
 };
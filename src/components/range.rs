@@ -0,0 +1,44 @@
+use super::scalor::Scalor;
+
+
+/// `lower ≤ (the value) ≤ upper`, where `lower` or `upper` may be infinite.
+/// 
+/// With `serde` feature, an infinite bound is serialized as `null` ( as JSON has no infinity ).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Range {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "infinite::serialize", deserialize_with = "infinite::deserialize_lower"))]
+    pub lower: Scalor,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "infinite::serialize", deserialize_with = "infinite::deserialize_upper"))]
+    pub upper: Scalor,
+}
+
+impl Range {
+    /// `0 ≤ (the value)`, the default bounds of variables
+    pub const NONNEGATIVE: Range = Range { lower: 0., upper: Scalor::INFINITY };
+    /// No bound
+    pub const FREE:        Range = Range { lower: -Scalor::INFINITY, upper: Scalor::INFINITY };
+
+    /// Set the upper bound read from MPS or LP format. Unless the lower bound is given,
+    /// a negative upper bound makes the default lower bound 0 into -∞ instead of leaving the variable infeasible.
+    pub(crate) fn set_upper(&mut self, upper: Scalor, lower_given: bool) {
+        if upper < 0. && self.lower == 0. && !lower_given {self.lower = -Scalor::INFINITY}
+        self.upper = upper
+    }
+}
+
+#[cfg(feature = "serde")]
+mod infinite {
+    use serde::{Serialize, Serializer, Deserialize, Deserializer};
+    use super::Scalor;
+
+    pub(super) fn serialize<S: Serializer>(bound: &Scalor, serializer: S) -> Result<S::Ok, S::Error> {
+        bound.is_finite().then_some(bound).serialize(serializer)
+    }
+    pub(super) fn deserialize_lower<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Scalor, D::Error> {
+        Ok(Option::deserialize(deserializer)?.unwrap_or(-Scalor::INFINITY))
+    }
+    pub(super) fn deserialize_upper<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Scalor, D::Error> {
+        Ok(Option::deserialize(deserializer)?.unwrap_or(Scalor::INFINITY))
+    }
+}
@@ -0,0 +1,216 @@
+use super::{scalor::Scalor, matrix::Matrix};
+use crate::error::MatrixError;
+
+
+/// Matrix in compressed sparse column ( CSC ) form, which stores only nonzero entries.
+/// As `Matrix`, `row_size` is the length of a row and `column_size` is the length of a column.
+/// 
+/// With `serde` feature, serialized as `{ "row_size", "column_size", "entries": [[row index, column index, value], ...] }`.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "Triplets", try_from = "Triplets"))]
+pub struct SparseMatrix {
+    pub row_size:    usize,
+    pub column_size: usize,
+    /// Entries of column `j` are at `column_starts[j]..column_starts[j + 1]`
+    column_starts:   Vec<usize>,
+    /// Ascending in each column
+    row_indices:     Vec<usize>,
+    values:          Vec<Scalor>,
+}
+
+impl SparseMatrix {
+    pub fn zeroed(row_size: usize, column_size: usize) -> Self {
+        Self {
+            row_size,
+            column_size,
+            column_starts: vec![0; row_size + 1],
+            row_indices:   Vec::new(),
+            values:        Vec::new(),
+        }
+    }
+
+    /// Build from `(row index, column index, value)`s. Values at the same position are summed up.
+    pub fn from_triplets(row_size: usize, column_size: usize, triplets: impl IntoIterator<Item = (usize, usize, Scalor)>) -> Result<Self, MatrixError> {
+        let mut triplets = triplets.into_iter().collect::<Vec<_>>();
+        for &(i, j, _) in &triplets {
+            if i >= column_size {return Err(MatrixError::IndexOutOfBound { size: column_size, index: i })}
+            if j >= row_size    {return Err(MatrixError::IndexOutOfBound { size: row_size,    index: j })}
+        }
+        triplets.sort_by_key(|&(i, j, _)| (j, i));
+
+        let mut matrix = Self::zeroed(row_size, column_size);
+        let mut triplets = triplets.into_iter().peekable();
+        for j in 0..row_size {
+            while let Some((i, _, mut value)) = triplets.next_if(|&(_, column, _)| column == j) {
+                while let Some((_, _, another)) = triplets.next_if(|&(row, column, _)| (row, column) == (i, j)) {
+                    value += another
+                }
+                if value != 0. {
+                    matrix.row_indices.push(i);
+                    matrix.values.push(value);
+                }
+            }
+            matrix.column_starts[j + 1] = matrix.values.len();
+        }
+        Ok(matrix)
+    }
+
+    /// Number of the stored entries
+    pub fn nonzeros(&self) -> usize {
+        self.values.len()
+    }
+
+    /// `(row index, value)` of each nonzero entry in the column
+    pub fn column(&self, index: usize) -> Result<impl Iterator<Item = (usize, Scalor)> + '_, MatrixError> {
+        (index < self.row_size)
+            .then(|| {
+                let range = self.column_starts[index]..self.column_starts[index + 1];
+                self.row_indices[range.clone()].iter().copied().zip(self.values[range].iter().copied())
+            })
+            .ok_or(MatrixError::IndexOutOfBound { size: self.row_size, index })
+    }
+
+    /// Values of all the nonzero entries
+    pub fn nonzero_values(&self) -> impl Iterator<Item = &Scalor> {
+        self.values.iter()
+    }
+
+    /// Apply `f` to each nonzero entry of the column
+    pub fn map_column(&mut self, index: usize, f: impl Fn(Scalor) -> Scalor) -> Result<(), MatrixError> {
+        if index >= self.row_size {
+            return Err(MatrixError::IndexOutOfBound { size: self.row_size, index })
+        }
+        for value in &mut self.values[self.column_starts[index]..self.column_starts[index + 1]] {
+            *value = f(*value)
+        }
+        Ok(())
+    }
+
+    /// Apply `f` to each nonzero entry with its row index
+    pub fn map_entries(&mut self, f: impl Fn(usize, Scalor) -> Scalor) {
+        for (value, &i) in self.values.iter_mut().zip(&self.row_indices) {
+            *value = f(i, *value)
+        }
+    }
+
+    /// Add a column given by `(row index, value)`s in any order. Values at the same row are summed up.
+    pub fn push_column(&mut self, column: impl IntoIterator<Item = (usize, Scalor)>) -> Result<(), MatrixError> {
+        let mut column = column.into_iter().collect::<Vec<_>>();
+        if let Some(&(i, _)) = column.iter().find(|&&(i, _)| i >= self.column_size) {
+            return Err(MatrixError::IndexOutOfBound { size: self.column_size, index: i })
+        }
+        column.sort_by_key(|&(i, _)| i);
+
+        let mut column = column.into_iter().peekable();
+        while let Some((i, mut value)) = column.next() {
+            while let Some((_, another)) = column.next_if(|&(row, _)| row == i) {
+                value += another
+            }
+            if value != 0. {
+                self.row_indices.push(i);
+                self.values.push(value);
+            }
+        }
+        self.column_starts.push(self.values.len());
+        self.row_size += 1;
+        Ok(())
+    }
+
+    /// Add a row given by `(column index, value)`s
+    pub fn push_row(&mut self, row: impl IntoIterator<Item = (usize, Scalor)>) -> Result<(), MatrixError> {
+        self.push_rows([row])
+    }
+
+    /// Add rows given by `(column index, value)`s at once, moving the stored entries only once.
+    /// Values at the same column of a row are summed up.
+    pub fn push_rows<Row: IntoIterator<Item = (usize, Scalor)>>(&mut self, rows: impl IntoIterator<Item = Row>) -> Result<(), MatrixError> {
+        let mut new_entries = Vec::new();
+        let mut column_size = self.column_size;
+        for row in rows {
+            for (j, value) in row {
+                if j >= self.row_size {
+                    return Err(MatrixError::IndexOutOfBound { size: self.row_size, index: j })
+                }
+                new_entries.push((j, column_size, value))
+            }
+            column_size += 1;
+        }
+        new_entries.sort_by_key(|&(j, i, _)| (j, i));
+
+        let mut new_entries = new_entries.into_iter().peekable();
+        let (mut column_starts, mut row_indices, mut values) = (vec![0], Vec::new(), Vec::new());
+        for j in 0..self.row_size {
+            let range = self.column_starts[j]..self.column_starts[j + 1];
+            row_indices.extend_from_slice(&self.row_indices[range.clone()]);
+            values.extend_from_slice(&self.values[range]);
+            while let Some((_, i, mut value)) = new_entries.next_if(|&(column, ..)| column == j) {
+                while let Some((.., another)) = new_entries.next_if(|&(column, row, _)| (column, row) == (j, i)) {
+                    value += another
+                }
+                if value != 0. {
+                    row_indices.push(i);
+                    values.push(value);
+                }
+            }
+            column_starts.push(values.len());
+        }
+        self.column_starts = column_starts;
+        self.row_indices   = row_indices;
+        self.values        = values;
+        self.column_size = column_size;
+        Ok(())
+    }
+
+    pub fn to_dense(&self) -> Matrix<Scalor> {
+        let mut matrix = Matrix::zeroed(self.row_size, self.column_size);
+        for j in 0..self.row_size {
+            for (i, value) in self.column(j).unwrap() {
+                matrix[i][j] = value
+            }
+        }
+        matrix
+    }
+}
+
+const _: () = {
+    impl From<Matrix<Scalor>> for SparseMatrix {
+        fn from(matrix: Matrix<Scalor>) -> Self {
+            let triplets = matrix.rows.iter().enumerate()
+                .flat_map(|(i, row)| row.iter().enumerate().map(move |(j, value)| (i, j, *value)));
+            SparseMatrix::from_triplets(matrix.row_size, matrix.column_size, triplets).unwrap()
+        }
+    }
+
+    impl std::fmt::Debug for SparseMatrix {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            self.to_dense().fmt(f)
+        }
+    }
+};
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Triplets {
+    row_size:    usize,
+    column_size: usize,
+    entries:     Vec<(usize, usize, Scalor)>,
+}
+#[cfg(feature = "serde")]
+const _: () = {
+    impl From<SparseMatrix> for Triplets {
+        fn from(matrix: SparseMatrix) -> Self {
+            let entries = (0..matrix.row_size)
+                .flat_map(|j| matrix.column(j).unwrap().map(move |(i, value)| (i, j, value)))
+                .collect();
+            Triplets { row_size: matrix.row_size, column_size: matrix.column_size, entries }
+        }
+    }
+
+    impl TryFrom<Triplets> for SparseMatrix {
+        type Error = MatrixError;
+        fn try_from(Triplets { row_size, column_size, entries }: Triplets) -> Result<Self, Self::Error> {
+            SparseMatrix::from_triplets(row_size, column_size, entries)
+        }
+    }
+};
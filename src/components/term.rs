@@ -2,7 +2,8 @@ use std::ops::{Mul};
 use super::{scalor::Scalor, variable::Variable};
 
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Term {
     pub coefficient: Scalor,
     pub variable:    Variable,
@@ -25,8 +26,9 @@ const _: () = {
             }
         )*};
     } mul_variable! {
-        i8 i16 i32 /*i64 i128 */
-        u8 u16 u32 /*u64 u128 */
-        f32 f64
+        /* Only one integer type and one float type, so that the types of literals in
+           `(2*x1 + 0.5*x2).le(20)` are inferred before the method call.
+           A coefficient of another type is converted into `Scalor` first: `Scalor::from(2u8)*x1` */
+        i32 f64
     }
 };
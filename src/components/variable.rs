@@ -1,35 +1,137 @@
+/// A variable of a problem, or one that the solver adds.
+/// 
+/// With `serde` feature, serialized as an externally tagged enum:
+/// `{"Normal": "x1"}`, `{"Negative": "x1"}`, `{"Slack": 1}`, `{"Surplus": 1}`, `{"Artificial": 1}` or `"Object"`.
+/// Maps keyed by variables are serialized as sequences of `[variable, value]` pairs.
 #[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(from = "Tagged", into = "Tagged"))]
 pub enum Variable {
     Normal {name: String},
+    /// `x⁻` of a free variable `x` split into `x = x⁺ - x⁻`, where `x⁺` keeps the `Normal` one
+    Negative {name: String},
     Slack  {id:   usize},
+    Surplus {id:  usize},
+    Artificial {id: usize},
     Object,
 } impl Variable {
     pub fn is_normal(&self) -> bool {
         matches!(self, Variable::Normal {..})
     }
+    pub fn is_negative(&self) -> bool {
+        matches!(self, Variable::Negative {..})
+    }
+    /// Normal variables and negative parts of free ones, whose columns come from the problem's `A`
+    pub fn is_structural(&self) -> bool {
+        self.is_normal() || self.is_negative()
+    }
     pub fn is_slack(&self) -> bool {
         matches!(self, Variable::Slack {..})
     }
+    pub fn is_surplus(&self) -> bool {
+        matches!(self, Variable::Surplus {..})
+    }
+    pub fn is_artificial(&self) -> bool {
+        matches!(self, Variable::Artificial {..})
+    }
+}
+
+/// Which values a variable can take, in addition to its bounds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VariableKind {
+    Continuous,
+    Integer,
+    /// Integer in `0 ≤ x ≤ 1`
+    Binary,
+} impl VariableKind {
+    pub fn is_integer(&self) -> bool {
+        matches!(self, VariableKind::Integer | VariableKind::Binary)
+    }
 }
 
-pub fn var(name: &'static str) -> Variable {
+pub fn var(name: impl Into<String>) -> Variable {
     Variable::Normal {
-        name: name.to_string(),
+        name: name.into(),
     }
 }
 
 #[cfg(test)] pub(crate) fn slack(id: usize) -> Variable {
     Variable::Slack { id }
 }
+#[cfg(test)] pub(crate) fn surplus(id: usize) -> Variable {
+    Variable::Surplus { id }
+}
+#[cfg(test)] pub(crate) fn artificial(id: usize) -> Variable {
+    Variable::Artificial { id }
+}
 
 const _: () = {
     impl std::fmt::Debug for Variable {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             match self {
                 Self::Normal { name } => f.write_str(name),
+                Self::Negative { name } => f.write_str(&format!("neg#{name}")),
                 Self::Slack  {  id  } => f.write_str(&format!("slack#{id}")),
+                Self::Surplus {  id } => f.write_str(&format!("surplus#{id}")),
+                Self::Artificial { id } => f.write_str(&format!("artificial#{id}")),
                 Self::Object          => f.write_str("#z"),
             }
         }
     }
 };
+
+/// `Variable` with its fields unnamed, for the externally tagged representation
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+enum Tagged {
+    Normal(String),
+    Negative(String),
+    Slack(usize),
+    Surplus(usize),
+    Artificial(usize),
+    Object,
+}
+#[cfg(feature = "serde")]
+const _: () = {
+    impl From<Tagged> for Variable {
+        fn from(tagged: Tagged) -> Self {
+            match tagged {
+                Tagged::Normal(name)   => Variable::Normal { name },
+                Tagged::Negative(name) => Variable::Negative { name },
+                Tagged::Slack(id)      => Variable::Slack { id },
+                Tagged::Surplus(id)    => Variable::Surplus { id },
+                Tagged::Artificial(id) => Variable::Artificial { id },
+                Tagged::Object         => Variable::Object,
+            }
+        }
+    }
+    impl From<Variable> for Tagged {
+        fn from(variable: Variable) -> Self {
+            match variable {
+                Variable::Normal { name }   => Tagged::Normal(name),
+                Variable::Negative { name } => Tagged::Negative(name),
+                Variable::Slack { id }      => Tagged::Slack(id),
+                Variable::Surplus { id }    => Tagged::Surplus(id),
+                Variable::Artificial { id } => Tagged::Artificial(id),
+                Variable::Object            => Tagged::Object,
+            }
+        }
+    }
+};
+
+/// `#[serde(with = "...")]` for `HashMap<Variable, T>` as a sequence of `[variable, value]` pairs,
+/// as variables can't be keys of JSON objects
+#[cfg(feature = "serde")]
+pub(crate) mod map {
+    use std::collections::HashMap;
+    use serde::{Serialize, Serializer, Deserialize, Deserializer};
+    use super::Variable;
+
+    pub(crate) fn serialize<T: Serialize, S: Serializer>(map: &HashMap<Variable, T>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(map)
+    }
+
+    pub(crate) fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<HashMap<Variable, T>, D::Error> {
+        Ok(Vec::<(Variable, T)>::deserialize(deserializer)?.into_iter().collect())
+    }
+}
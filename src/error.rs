@@ -0,0 +1,153 @@
+use std::time::Duration;
+use crate::components::variable::Variable;
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatrixError {
+    /// Constructed from no rows
+    EmptyRows,
+    /// A row's length doesn't match the row size of the matrix
+    RowSizeMismatch    { expected: usize, found: usize },
+    /// A column's length doesn't match the column size of the matrix
+    ColumnSizeMismatch { expected: usize, found: usize },
+    /// An index is not less than the size of the matrix in its dimension
+    IndexOutOfBound    { size: usize, index: usize },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolveError {
+    /// No solution satisfies all the constraints
+    Infeasible,
+    /// The objective function can be increased (or decreased when minimizing) without limit
+    Unbounded,
+    /// Pivot operations were repeated `iterations` times without reaching optimum
+    IterationLimit { iterations: usize },
+    /// `SolverOptions::time_limit` passed before reaching optimum
+    TimeLimit { elapsed: Duration },
+    /// Branch-and-bound solved `nodes` LP relaxations without finding any integer solution
+    NodeLimit { nodes: usize },
+    /// Sizes of the components of a `Condition` don't match each other
+    DimensionMismatch { name: &'static str, expected: usize, found: usize },
+    /// The objective function, a bound or a kind has a variable that is not a column of the `Condition`
+    UnknownVariable { variable: Variable },
+    /// A column of the `Condition` is not `Variable::Normal`: the others are made by the solver
+    NotNormalColumn { variable: Variable },
+    /// A value in the table got `NaN` or infinite
+    NumericalTrouble,
+    /// `Table::reoptimize` got a negative base variable on a table that isn't dual feasible
+    NotDualFeasible,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModelError {
+    /// `Model::add_var` was called twice with the same name
+    DuplicateVariable  { name: String },
+    /// The objective function or a constraint uses a variable not added by `Model::add_var`
+    UndeclaredVariable { name: String },
+    /// Neither `Model::maximize` nor `Model::minimize` was called
+    NoObjective,
+}
+
+/// A pivot operation of `Table::pivot_on`, or the pivot of `Table::pivot`, is not possible
+#[derive(Debug, Clone, PartialEq)]
+pub enum PivotError {
+    /// The row is not a constraint row of the table
+    RowOutOfBound    { size: usize, index: usize },
+    /// The column is not a column of the table
+    ColumnOutOfBound { size: usize, index: usize },
+    /// The element at the row and the column is 0 within `SolverOptions::pivot_tolerance`
+    ZeroElement      { row: usize, column: usize },
+    /// No element of the entering column is positive, so the objective function increases without limit along it
+    Unbounded        { column: usize },
+}
+
+/// Failure to read a `Problem` from a text format
+#[derive(Debug)]
+pub enum FormatError {
+    Io(std::io::Error),
+    /// The input doesn't follow the format at `line` and `column`, both starting from 1
+    Syntax { line: usize, column: usize, message: String },
+}
+
+impl FormatError {
+    pub(crate) fn syntax(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self::Syntax { line, column, message: message.into() }
+    }
+}
+
+const _: () = {
+    impl std::fmt::Display for MatrixError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::EmptyRows                              => f.write_str("Got empty rows in input"),
+                Self::RowSizeMismatch    { expected, found } => write!(f, "Row size is {expected} but new row's size is {found}"),
+                Self::ColumnSizeMismatch { expected, found } => write!(f, "Column size is {expected} but new column's size is {found}"),
+                Self::IndexOutOfBound    { size, index }     => write!(f, "Index out of bound: size is {size} but given index is {index}"),
+            }
+        }
+    }
+    impl std::error::Error for MatrixError {}
+
+    impl std::fmt::Display for SolveError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Infeasible                                  => f.write_str("Infeasible: no solution satisfies all the constraints"),
+                Self::Unbounded                                   => f.write_str("Unbounded: the objective function has no optimum"),
+                Self::IterationLimit    { iterations }            => write!(f, "Reached iteration limit: {iterations} pivot operations"),
+                Self::TimeLimit         { elapsed }               => write!(f, "Reached time limit: {elapsed:?} elapsed"),
+                Self::NodeLimit         { nodes }                 => write!(f, "Reached node limit: {nodes} LP relaxations solved without integer solution"),
+                Self::DimensionMismatch { name, expected, found } => write!(f, "`{name}` is expected to have {expected} elements but has {found}"),
+                Self::UnknownVariable   { variable }              => write!(f, "`{variable:?}` is not a column of the condition"),
+                Self::NotNormalColumn   { variable }              => write!(f, "`{variable:?}` is a column of the condition but not a normal variable"),
+                Self::NumericalTrouble                            => f.write_str("Numerical trouble: got NaN or infinite value in the table"),
+                Self::NotDualFeasible                             => f.write_str("Not dual feasible: rows can be added only to an optimal table to reoptimize it"),
+            }
+        }
+    }
+    impl std::error::Error for SolveError {}
+
+    impl std::fmt::Display for ModelError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::DuplicateVariable  { name } => write!(f, "Variable `{name}` is added more than once"),
+                Self::UndeclaredVariable { name } => write!(f, "Variable `{name}` is used but not added to the model"),
+                Self::NoObjective                 => f.write_str("No objective function is set to the model"),
+            }
+        }
+    }
+    impl std::error::Error for ModelError {}
+
+    impl std::fmt::Display for PivotError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::RowOutOfBound    { size, index } => write!(f, "Row out of bound: the table has {size} constraint rows but given row is {index}"),
+                Self::ColumnOutOfBound { size, index } => write!(f, "Column out of bound: the table has {size} columns but given column is {index}"),
+                Self::ZeroElement      { row, column } => write!(f, "The element at row {row} and column {column} is 0"),
+                Self::Unbounded        { column }      => write!(f, "Unbounded: no element of the entering column {column} is positive"),
+            }
+        }
+    }
+    impl std::error::Error for PivotError {}
+
+    impl std::fmt::Display for FormatError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Io(error)                         => write!(f, "Failed to read: {error}"),
+                Self::Syntax { line, column, message } => write!(f, "Line {line}, column {column}: {message}"),
+            }
+        }
+    }
+    impl std::error::Error for FormatError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            match self {
+                Self::Io(error) => Some(error),
+                Self::Syntax { .. } => None,
+            }
+        }
+    }
+    impl From<std::io::Error> for FormatError {
+        fn from(error: std::io::Error) -> Self {
+            Self::Io(error)
+        }
+    }
+};
@@ -6,6 +6,11 @@
 //!     ( x, b, c \in R^n,  A \in R^m × R^n ) 
 //! ```
 //! 
+//! ( Each row of `A x <= b` can also be `>=` or `=`: see [`Sign`] )\
+//! ( `minimize: c x` is solved as `maximize: -c x`: see [`Sense`] )\
+//! ( Variables with other bounds than `x_i >= 0` are replaced by nonnegative ones: see [`Problem::bound`] )\
+//! ( Integer variables are handled by branch-and-bound: see [`Problem::integer`] )
+//! 
 //! This can be transformed to "standarnd form":
 //! ```ignore
 //!     maximize:  c x
@@ -21,34 +26,88 @@
 //! --> a_{i1} x_1 + a_{i2} x_2 + ... + a_{in} x_n  + s_i  = b_i,  s_i >= 0
 //! ```
 //! 
-
-#![feature(slice_concat_trait)]
+//! and for a `>=` row, a "surplus variable" is subtracted in the same way:
+//! 
+//! ```ignore
+//!     a_{i1} x_1 + a_{i2} x_2 + ... + a_{in} x_n  >=  b_i
+//! --> a_{i1} x_1 + a_{i2} x_2 + ... + a_{in} x_n  - s_i  = b_i,  s_i >= 0
+//! ```
+//! 
+//! When some `b_i` is negative, the origin `x = 0` is not feasible and slack variables
+//! alone can't make the initial basis. Then "Phase I" introduces artificial variables
+//! for such rows and searches a feasible basis by minimizing their sum, before
+//! "Phase II" optimizes the objective function from it.
+//! 
 
 #[cfg(not(target_pointer_width = "64"))]
 compile_error!{ "Not support targets where pointer width is not 64" }
 
 mod components;
+mod error;
+mod model;
+mod options;
 mod problem;
 mod table;
 
 
 pub use components::{
-    variable::var,
+    variable::{var, Variable, VariableKind},
+    polynomial::Polynomial,
+    term::Term,
     matrix::Matrix,
+    sparse::SparseMatrix,
+    range::Range,
+};
+pub use error::{
+    FormatError,
+    MatrixError,
+    ModelError,
+    PivotError,
+    SolveError,
+};
+pub use model::{
+    Model,
+    VariableBuilder,
+};
+pub use options::{
+    Algorithm,
+    PivotRule,
+    SolverOptions,
 };
 pub use problem::{
     Condition,
+    Constraint,
     Problem,
+    Sense,
+    Sign,
 };
 pub use table::{
+    Phase,
+    Pivot,
+    SensitivityReport,
+    SimplexSteps,
     Solution,
+    Step,
+    Table,
 };
 
 impl Problem {
-    pub fn solve(self) -> Result<Solution, String> {
-        table::Table::from_problem(
-            self.into_standard_form()
-        ).solve()
+    pub fn solve(self) -> Result<Solution, SolveError> {
+        self.solve_with(SolverOptions::default())
+    }
+
+    pub fn solve_with(self, options: SolverOptions) -> Result<Solution, SolveError> {
+        self.validate()?;
+        if self.kinds.values().any(VariableKind::is_integer) {
+            self.branch_and_bound(&options)
+        } else {
+            Table::solve_relaxation(self, &options)
+        }
+    }
+
+    /// Solve step by step on the table of [`Table::new`]. See [`Table::steps`].
+    pub fn steps(self, options: SolverOptions) -> Result<SimplexSteps, SolveError> {
+        Ok(Table::new(self)?.steps(options))
     }
 }
 
@@ -70,7 +129,7 @@ macro_rules! matrix {
     };
 }
 
-#[cfg(test)] #[test] fn test_matrix_macro() {
+#[cfg(test)] #[test] #[allow(clippy::approx_constant)] fn test_matrix_macro() {
     let m = matrix! {
         1., 2.
     };
@@ -92,3 +151,114 @@ macro_rules! matrix {
     assert_eq!(m.row_size,    4);
     assert_eq!(m.column_size, 3);
 }
+
+#[cfg(test)] #[test] fn test_matrix_errors() {
+    assert_eq!(
+        Matrix::try_from(vec![vec![1., 2.], vec![3., 4.], vec![5.]]),
+        Err(MatrixError::RowSizeMismatch { expected: 2, found: 1 })
+    );
+    assert_eq!(
+        Matrix::try_from(Vec::<Vec<f64>>::new()),
+        Err(MatrixError::EmptyRows)
+    );
+
+    let mut m = matrix! {
+        1., 2.
+        3., 4.
+    };
+    assert_eq!(m.push_row(vec![5.]), Err(MatrixError::RowSizeMismatch { expected: 2, found: 1 }));
+    assert_eq!(m.column_iter(2).err(), Some(MatrixError::IndexOutOfBound { size: 2, index: 2 }));
+    assert_eq!(
+        m.try_concat(Matrix::identity(3)),
+        Err(MatrixError::ColumnSizeMismatch { expected: 2, found: 3 })
+    );
+}
+
+#[cfg(test)] #[test] fn test_sparse_matrix() {
+    let mut m = SparseMatrix::from(matrix! {
+        1, 0, 2
+        0, 0, 3
+    });
+    assert_eq!(m.nonzeros(), 3);
+    assert_eq!(m.column(2).unwrap().collect::<Vec<_>>(), vec![(0, 2.), (1, 3.)]);
+    assert_eq!(m.column(1).unwrap().count(), 0);
+
+    m.push_column([(1, -1.)]).unwrap();
+    m.push_rows([vec![(1, 4.)], vec![(3, 5.), (0, 6.)]]).unwrap();
+    m.map_column(0, |a| -a).unwrap();
+    assert_eq!(m.to_dense(), matrix! {
+        -1, 0, 2,  0
+         0, 0, 3, -1
+         0, 4, 0,  0
+        -6, 0, 0,  5
+    });
+    assert_eq!(m.nonzeros(), 7);
+
+    /* Multiply the row 3 by -1 */
+    m.map_entries(|i, a| if i == 3 {-a} else {a});
+    assert_eq!(m.column(0).unwrap().collect::<Vec<_>>(), vec![(0, -1.), (3, 6.)]);
+    assert_eq!(m.column(3).unwrap().collect::<Vec<_>>(), vec![(1, -1.), (3, -5.)]);
+
+    assert_eq!(
+        SparseMatrix::from_triplets(2, 2, [(1, 0, 1.), (0, 1, 2.), (1, 0, 3.), (0, 1, -2.)]).unwrap(),
+        SparseMatrix::from(matrix! {
+            0, 0
+            4, 0
+        })
+    );
+
+    assert_eq!(
+        SparseMatrix::from_triplets(2, 2, [(0, 2, 1.)]),
+        Err(MatrixError::IndexOutOfBound { size: 2, index: 2 })
+    );
+    assert_eq!(m.column(4).err(), Some(MatrixError::IndexOutOfBound { size: 4, index: 4 }));
+    assert_eq!(m.push_column([(0, 1.), (4, 1.)]), Err(MatrixError::IndexOutOfBound { size: 4, index: 4 }));
+    assert_eq!(m.row_size, 4);
+
+    /* Unsorted and duplicated entries are sorted and summed up, dropping zeros */
+    let mut m = SparseMatrix::zeroed(0, 3);
+    m.push_column([(2, 1.), (0, 2.), (2, 3.), (1, 1.), (1, -1.)]).unwrap();
+    m.push_rows([vec![(0, 1.), (0, 1.)]]).unwrap();
+    assert_eq!(m.column(0).unwrap().collect::<Vec<_>>(), vec![(0, 2.), (2, 4.), (3, 2.)]);
+    assert_eq!(m.nonzeros(), 3);
+}
+
+#[cfg(test)] #[test] fn test_factorization() {
+    use components::factorization::Factorization;
+
+    /* Columns of B, which needs row exchanges: the first column's largest entry is in the row 2 */
+    let columns = vec![
+        vec![(0, 1.), (1, 2.), (2, 4.)],
+        vec![(0, 3.)],
+        vec![(1, 1.), (2, 1.)],
+    ];
+    let (mut factorization, rows) = Factorization::new(&columns, 1e-9).unwrap();
+
+    /* `B` has `columns[k]` as its column `rows[k]` */
+    let mut b = Matrix::zeroed(3, 3);
+    for (column, &row) in columns.iter().zip(&rows) {
+        for &(i, a) in column {
+            b[i][row] = a;
+        }
+    }
+    let close = |x: &[f64], y: &[f64]| x.iter().zip(y).all(|(a, b)| (a - b).abs() < 1e-9);
+    let product = |b: &Matrix<f64>, x: &[f64]| (0..3).map(|i| (0..3).map(|j| b[i][j] * x[j]).sum()).collect::<Vec<f64>>();
+    let transposed = |b: &Matrix<f64>, y: &[f64]| (0..3).map(|j| (0..3).map(|i| y[i] * b[i][j]).sum()).collect::<Vec<f64>>();
+
+    let v = [1., -2., 5.];
+    assert!(close(&product(&b, &factorization.ftran(&v)), &v));
+    assert!(close(&transposed(&b, &factorization.btran(&v)), &v));
+
+    /* Replace the column 0 of B by `a` */
+    let a = [2., 0., 1.];
+    factorization.update(0, factorization.ftran(&a));
+    for i in 0..3 {
+        b[i][0] = a[i];
+    }
+    assert_eq!(factorization.updates(), 1);
+    assert!(close(&product(&b, &factorization.ftran(&v)), &v));
+    assert!(close(&transposed(&b, &factorization.btran(&v)), &v));
+
+    /* Singular */
+    assert!(Factorization::new(&[vec![(0, 1.), (1, 2.)], vec![(0, 2.), (1, 4.)]], 1e-9).is_none());
+}
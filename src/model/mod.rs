@@ -0,0 +1,138 @@
+#[cfg(test)] mod test;
+
+use std::collections::HashMap;
+use crate::problem::{Problem, Condition, Constraint, Sense};
+use crate::error::ModelError;
+use crate::components::{polynomial::Polynomial, scalor::Scalor, variable::{Variable, VariableKind}, range::Range};
+
+
+/// Builder of a `Problem` whose variables are added one by one with owned names:
+/// ```ignore
+/// let mut model = Model::new();
+/// let x = model.add_var("steel_42").bounds(0.0, 100.0).handle();
+/// let n = model.add_var("trucks").integer().handle();
+/// let y = model.add_var(format!("product_{id}")).handle();
+/// model.add_constraint(Constraint::le(2*&x + 5*&y, 20));
+/// model.maximize(4*&x + 5*&y);
+/// let problem = model.into_problem()?;
+/// ```
+#[derive(Debug, Default)]
+pub struct Model {
+    variables:   Vec<(Variable, Range, VariableKind)>,
+    constraints: Vec<Constraint>,
+    objective:   Option<(Sense, Polynomial)>,
+    duplicates:  Vec<String>,
+}
+
+/// Returned by `Model::add_var` to set the variable's bounds
+pub struct VariableBuilder<'m> {
+    model: &'m mut Model,
+    index: usize,
+}
+
+impl Model {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a variable `0 ≤ name`. Adding the same name twice makes `into_problem` fail.
+    pub fn add_var(&mut self, name: impl Into<String>) -> VariableBuilder<'_> {
+        let variable = Variable::Normal { name: name.into() };
+        if let Some(index) = self.variables.iter().position(|(v, ..)| v == &variable) {
+            let Variable::Normal { name } = variable else {unreachable!()};
+            self.duplicates.push(name);
+            return VariableBuilder { model: self, index }
+        }
+
+        self.variables.push((variable, Range::NONNEGATIVE, VariableKind::Continuous));
+        VariableBuilder { index: self.variables.len() - 1, model: self }
+    }
+
+    pub fn add_constraint(&mut self, constraint: Constraint) -> &mut Self {
+        self.constraints.push(constraint);
+        self
+    }
+
+    pub fn maximize(&mut self, objective_function: impl Into<Polynomial>) -> &mut Self {
+        self.objective = Some((Sense::Maximize, objective_function.into()));
+        self
+    }
+
+    pub fn minimize(&mut self, objective_function: impl Into<Polynomial>) -> &mut Self {
+        self.objective = Some((Sense::Minimize, objective_function.into()));
+        self
+    }
+
+    /// Build the `Problem`, whose columns are the variables in the order they were added
+    pub fn into_problem(self) -> Result<Problem, ModelError> {
+        let Model { variables, constraints, objective, duplicates } = self;
+
+        if let Some(name) = duplicates.into_iter().next() {
+            return Err(ModelError::DuplicateVariable { name })
+        }
+        let (sense, objective_function) = objective.ok_or(ModelError::NoObjective)?;
+
+        let used_variables = objective_function.terms.iter()
+            .chain(constraints.iter().flat_map(|constraint| &constraint.lhs.terms))
+            .map(|term| &term.variable);
+        for variable in used_variables {
+            if !variables.iter().any(|(v, ..)| v == variable) {
+                return Err(ModelError::UndeclaredVariable { name: format!("{variable:?}") })
+            }
+        }
+
+        let bounds = variables.iter()
+            .filter(|(_, range, _)| range != &Range::NONNEGATIVE)
+            .map(|(variable, range, _)| (variable.clone(), *range))
+            .collect::<HashMap<_, _>>();
+        let kinds = variables.iter()
+            .filter(|(.., kind)| kind != &VariableKind::Continuous)
+            .map(|(variable, _, kind)| (variable.clone(), *kind))
+            .collect::<HashMap<_, _>>();
+        let x = variables.into_iter().map(|(variable, ..)| variable).collect();
+
+        Ok(Problem {
+            sense,
+            objective_function,
+            condition: Condition::assemble(x, constraints),
+            bounds,
+            kinds,
+        })
+    }
+}
+
+impl VariableBuilder<'_> {
+    /// `lower ≤ variable ≤ upper`. Either of them can be infinite.
+    pub fn bounds(self, lower: impl Into<Scalor>, upper: impl Into<Scalor>) -> Self {
+        self.model.variables[self.index].1 = Range { lower: lower.into(), upper: upper.into() };
+        self
+    }
+
+    /// The variable can take any value
+    pub fn free(self) -> Self {
+        self.bounds(-Scalor::INFINITY, Scalor::INFINITY)
+    }
+
+    /// The variable can take only integer values in its bounds
+    pub fn integer(self) -> Self {
+        self.model.variables[self.index].2 = VariableKind::Integer;
+        self
+    }
+
+    /// The variable can take only 0 or 1
+    pub fn binary(self) -> Self {
+        self.model.variables[self.index].2 = VariableKind::Binary;
+        self
+    }
+
+    /// The variable to be used in the objective function and constraints
+    pub fn handle(self) -> Variable {
+        self.model.variables[self.index].0.clone()
+    }
+}
+
+impl From<VariableBuilder<'_>> for Variable {
+    fn from(builder: VariableBuilder<'_>) -> Self {
+        builder.handle()
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+use crate::{*, components::{scalor::Scalor, range::Range}};
+
+
+#[test] fn test_model() {
+    let mut model = Model::new();
+    let ids = [42, 7];
+    let steel = model.add_var(format!("steel_{}", ids[0])).bounds(1, 3).handle();
+    let iron: Variable = model.add_var(format!("iron_{}", ids[1])).into();
+    let scrap = model.add_var("scrap").free().handle();
+
+    model
+        .add_constraint(Constraint::le(1*&steel + 1*&iron, 4))
+        .add_constraint(Constraint::eq(1*&scrap, -2))
+        .maximize(3*&steel + 2*&iron + 1*&scrap);
+
+    let problem = model.into_problem().unwrap();
+    assert_eq!(problem.bounds, HashMap::from([
+        (var("steel_42"), Range { lower: 1., upper: 3. }),
+        (var("scrap"),    Range { lower: -Scalor::INFINITY, upper: Scalor::INFINITY }),
+    ]));
+    assert_eq!(problem.condition, Condition::new(
+        matrix! {
+            1, 1, 0
+            0, 0, 1
+        },
+        vec![
+            var("steel_42"),
+            var("iron_7"),
+            var("scrap"),
+        ],
+        vec![
+            Sign::LE,
+            Sign::EQ,
+        ],
+        vec![
+            4,
+            -2,
+        ]
+    ));
+
+    let solution = problem.solve().unwrap();
+    assert_eq!(solution.variables, HashMap::from([
+        (var("steel_42"),  3.),
+        (var("iron_7"),    1.),
+        (var("scrap"),    -2.),
+    ]));
+    assert_eq!(solution.optimal_value, 9.);
+}
+
+#[test] fn test_model_integer_variables() {
+    let mut model = Model::new();
+    let trucks = model.add_var("trucks").integer().handle();
+    let depot  = model.add_var("depot").binary().handle();
+
+    model
+        .add_constraint(Constraint::le(1*&trucks - 10*&depot, 0))
+        .add_constraint(Constraint::ge(1*&trucks, 2.5))
+        .minimize(3*&trucks + 20*&depot);
+
+    let solution = model.into_problem().unwrap().solve().unwrap();
+    assert_eq!(solution.variables, HashMap::from([
+        (var("trucks"), 3.),
+        (var("depot"),  1.),
+    ]));
+    assert_eq!(solution.optimal_value, 29.);
+    assert_eq!(solution.mip_gap, Some(0.));
+}
+
+#[test] fn test_model_errors() {
+    let mut model = Model::new();
+    let x = model.add_var("x").handle();
+    model.add_var("x").bounds(0, 1);
+    model.maximize(1*&x);
+    assert_eq!(model.into_problem(), Err(ModelError::DuplicateVariable { name: "x".to_string() }));
+
+    let mut model = Model::new();
+    let x = model.add_var("x").handle();
+    model.add_constraint(Constraint::le(1*&x + 1*var("y"), 1));
+    model.maximize(1*&x);
+    assert_eq!(model.into_problem(), Err(ModelError::UndeclaredVariable { name: "y".to_string() }));
+
+    let mut model = Model::new();
+    model.add_var("x");
+    assert_eq!(model.into_problem(), Err(ModelError::NoObjective));
+}
@@ -0,0 +1,87 @@
+use std::time::Duration;
+use crate::components::scalor::Scalor;
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SolverOptions {
+    /// Maximum number of pivot operations through Phase I and II.
+    /// `None` means `50 × (number of rows + number of columns)` of the initial table.
+    pub max_iterations:           Option<usize>,
+    /// Wall-clock limit of the whole solving. `None` means no limit.
+    pub time_limit:               Option<Duration>,
+    /// A table is optimal when all simplex criterions are `>= -optimality_tolerance`
+    pub optimality_tolerance:     Scalor,
+    /// A problem is infeasible when Phase I ends with the sum of artificial variables `> feasibility_tolerance`
+    pub feasibility_tolerance:    Scalor,
+    /// Coefficients whose absolute value is `<= pivot_tolerance` are never chosen as a pivot
+    pub pivot_tolerance:          Scalor,
+    pub pivot_rule:               PivotRule,
+    pub algorithm:                Algorithm,
+    /// After this number of degenerate pivots in a row, `PivotRule::Dantzig` is switched to `PivotRule::Bland`
+    pub stall_limit:              usize,
+    /// `Algorithm::Revised` refactorizes the basis into fresh sparse LU factors after this number of product-form updates
+    pub refactorization_interval: usize,
+    /// A value of an integer variable is integral when it's within `integrality_tolerance` from the nearest integer
+    pub integrality_tolerance:    Scalor,
+    /// Branch-and-bound stops when the relative MIP gap gets `<= gap_tolerance`
+    pub gap_tolerance:            Scalor,
+    /// Maximum number of LP relaxations solved in branch-and-bound. `None` means no limit.
+    pub max_nodes:                Option<usize>,
+    /// Maximum number of Gomory fractional cuts added to the root LP relaxation of branch-and-bound
+    /// for a pure integer problem, whose variables are all integer and whose coefficients and bounds are all integral.
+    /// Enough cuts solve a small one without branching.
+    pub cut_rounds:               usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PivotRule {
+    /// Enter the variable whose simplex criterion is the most negative,
+    /// and leave the first row among ties of the ratio test
+    Dantzig,
+    /// Enter the first variable whose simplex criterion is negative,
+    /// and leave the base variable of the smallest column index among ties of the ratio test
+    Bland,
+    /// Enter as `Dantzig`, and break ties of the ratio test by comparing
+    /// the rows divided by the pivot column lexicographically
+    Lexicographic,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Algorithm {
+    /// Two-phase primal simplex method
+    Primal,
+    /// Dual simplex method from the basis of slack and surplus variables,
+    /// when its simplex criterions are all nonnegative ( e.g. minimizing nonnegative costs ).
+    /// Otherwise same as `Primal`.
+    Dual,
+    /// Two-phase revised simplex method, which keeps the sparse constraint matrix untouched and
+    /// maintains a sparse LU factorization of the basis with product-form updates. The solution of a linear problem is read
+    /// from the optimal basis, and only cuts and branch-and-bound build the dense table of it.
+    Revised,
+}
+
+impl Default for SolverOptions {
+    fn default() -> Self {
+        Self {
+            max_iterations:           None,
+            time_limit:               None,
+            optimality_tolerance:     1e-9,
+            feasibility_tolerance:    1e-9,
+            pivot_tolerance:          1e-9,
+            pivot_rule:               PivotRule::Dantzig,
+            algorithm:                Algorithm::Primal,
+            stall_limit:              50,
+            refactorization_interval: 50,
+            integrality_tolerance:    1e-6,
+            gap_tolerance:            1e-6,
+            max_nodes:                None,
+            cut_rounds:               0,
+        }
+    }
+}
+
+impl SolverOptions {
+    pub(crate) fn iteration_limit(&self, row_size: usize, column_size: usize) -> usize {
+        self.max_iterations.unwrap_or(50 * (row_size + column_size))
+    }
+}
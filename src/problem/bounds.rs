@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use crate::components::{scalor::Scalor, range::Range, variable::Variable, term::Term};
+use crate::table::Solution;
+use super::{Problem, Condition, Sign};
+
+
+/// How a variable with other bounds than `0 ≤ x` was replaced by a nonnegative one
+#[derive(Debug, Clone, PartialEq)]
+enum Replacement {
+    /// `x = lower + x'`, with a new row `x' ≤ upper - lower` when `upper` is finite
+    Shifted   { lower: Scalor, upper_row: Option<usize> },
+    /// `x = upper - x'`
+    Reflected { upper: Scalor },
+    /// `x = x⁺ - x⁻`
+    Split,
+}
+
+/// Record of `Problem::substitute_bounds` to restore the solution of the original problem.
+/// `Default` is the one that replaced nothing.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Substitution {
+    /// Number of the rows added for upper bounds after the original constraints
+    upper_rows:   usize,
+    /// Constant added to the objective function
+    constant:     Scalor,
+    /// Constant added to the left-hand side of each constraint that has one, with its row number
+    offsets:      Vec<(usize, Scalor)>,
+    replacements: Vec<(Variable, Replacement)>,
+}
+
+impl Problem {
+    /// Replace each variable that has other bounds than `0 ≤ x` by nonnegative one(s),
+    /// adding a `≤` row for each finite upper bound after the original constraints.
+    /// The constant of the objective function is also moved into `Substitution`.
+    pub(crate) fn substitute_bounds(self) -> (Problem, Substitution) {
+        let Problem { sense, mut objective_function, condition, bounds, kinds } = self;
+        let Condition { mut A, mut x, mut signs, mut b, .. } = condition;
+
+        let mut substitution = Substitution {
+            upper_rows:   0,
+            constant:     std::mem::take(&mut objective_function.constant),
+            offsets:      Vec::new(),
+            replacements: Vec::new(),
+        };
+        let mut offsets = vec![0.; b.len()];
+        let mut upper_rows = Vec::new();
+
+        for j in 0..x.len() {
+            let Range { lower, upper } = match bounds.get(&x[j]) {
+                Some(range) if range != &Range::NONNEGATIVE => *range,
+                _ => continue,
+            };
+            let coefficient = objective_function.terms.iter()
+                .find(|term| term.variable == x[j])
+                .map_or(0., |term| term.coefficient);
+
+            let replacement = if lower.is_finite() {
+                for (i, a) in A.column(j).unwrap() {
+                    b[i]       -= a * lower;
+                    offsets[i] += a * lower;
+                }
+                substitution.constant += coefficient * lower;
+
+                let upper_row = upper.is_finite().then(|| {
+                    upper_rows.push((j, upper - lower));
+                    upper_rows.len() - 1
+                });
+                Replacement::Shifted { lower, upper_row }
+
+            } else if upper.is_finite() {
+                for (i, a) in A.column(j).unwrap() {
+                    b[i]       -= a * upper;
+                    offsets[i] += a * upper;
+                }
+                A.map_column(j, |a| -a).unwrap();
+                substitution.constant += coefficient * upper;
+                for term in objective_function.terms.iter_mut().filter(|term| term.variable == x[j]) {
+                    term.coefficient = -term.coefficient;
+                }
+                Replacement::Reflected { upper }
+
+            } else {
+                let Variable::Normal { name } = &x[j] else {continue};
+                let negative = Variable::Negative { name: name.clone() };
+                A.push_column(A.column(j).unwrap().map(|(i, a)| (i, -a)).collect::<Vec<_>>()).unwrap();
+                objective_function += Term { coefficient: -coefficient, variable: negative.clone() };
+                x.push(negative);
+                Replacement::Split
+            };
+            substitution.replacements.push((x[j].clone(), replacement));
+        }
+
+        substitution.offsets = offsets.into_iter().enumerate().filter(|(_, offset)| *offset != 0.).collect();
+        substitution.upper_rows = upper_rows.len();
+        A.push_rows(upper_rows.iter().map(|&(j, _)| [(j, 1.)])).unwrap();
+        for (_, value) in upper_rows {
+            signs.push(Sign::LE);
+            b.push(value);
+        }
+
+        let problem = Problem {
+            sense,
+            objective_function,
+            condition: Condition { A, x, signs, b, names: Vec::new() },
+            bounds: Default::default(),
+            kinds,
+        };
+        (problem, substitution)
+    }
+}
+
+impl Substitution {
+    /// Convert the solution of the substituted problem into that of the original one.
+    /// Its optimal value has to include the constant already, as the table of `Table::from_problem` does.
+    pub(crate) fn restore(&self, mut solution: Solution) -> Solution {
+        let constraint_count = solution.duals.len() - self.upper_rows;
+        let upper_duals = solution.duals.split_off(constraint_count);
+
+        for vector in [&mut solution.slacks, &mut solution.activities] {
+            vector.truncate(constraint_count);
+        }
+        solution.binding.truncate(constraint_count);
+        solution.sensitivity.rhs_ranges.truncate(constraint_count);
+
+        for &(i, offset) in &self.offsets {
+            solution.activities[i]                   += offset;
+            solution.sensitivity.rhs_ranges[i].lower += offset;
+            solution.sensitivity.rhs_ranges[i].upper += offset;
+        }
+
+        self.restore_values(&mut solution.variables);
+        for (variable, replacement) in &self.replacements {
+            if !solution.variables.contains_key(variable) {continue}
+
+            match replacement {
+                Replacement::Shifted { upper_row, .. } => {
+                    /* At the upper bound, the bound row's shadow price is a part of the reduced cost */
+                    if let Some(row) = upper_row {
+                        *solution.reduced_costs.get_mut(variable).unwrap() += upper_duals[*row];
+                    }
+                }
+                Replacement::Reflected { .. } => {
+                    let reduced_cost = solution.reduced_costs.get_mut(variable).unwrap();
+                    *reduced_cost = -*reduced_cost;
+                    let range = solution.sensitivity.objective_ranges.get_mut(variable).unwrap();
+                    *range = Range { lower: -range.upper, upper: -range.lower };
+                }
+                Replacement::Split => {
+                    let Variable::Normal { name } = variable else {continue};
+                    let negative = Variable::Negative { name: name.clone() };
+                    solution.reduced_costs.remove(&negative);
+
+                    /* The coefficient of `x⁻` is the negative of `x`'s */
+                    let negative_range = solution.sensitivity.objective_ranges.remove(&negative).unwrap();
+                    let range = solution.sensitivity.objective_ranges.get_mut(variable).unwrap();
+                    *range = Range {
+                        lower: range.lower.max(-negative_range.upper),
+                        upper: range.upper.min(-negative_range.lower),
+                    };
+                }
+            }
+        }
+
+        solution
+    }
+
+    /// Convert the values of the substituted problem's variables into those of the original ones
+    pub(crate) fn restore_values(&self, values: &mut HashMap<Variable, Scalor>) {
+        for (variable, replacement) in &self.replacements {
+            let Some(mut value) = values.remove(variable) else {continue};
+            match replacement {
+                Replacement::Shifted { lower, .. } => value += lower,
+                Replacement::Reflected { upper }   => value = upper - value,
+                Replacement::Split => if let Variable::Normal { name } = variable {
+                    value -= values.remove(&Variable::Negative { name: name.clone() }).unwrap_or(0.)
+                }
+            }
+            values.insert(variable.clone(), value);
+        }
+    }
+
+    /// Constant of the objective function, which the substituted problem doesn't have
+    pub(crate) fn constant(&self) -> Scalor {
+        self.constant
+    }
+
+    /// `variable` of the original problem as `constant + Σ coefficient × column` of the substituted one
+    pub(crate) fn expression(&self, variable: &Variable) -> (Scalor, Vec<(Variable, Scalor)>) {
+        match self.replacements.iter().find(|(v, _)| v == variable).map(|(_, replacement)| replacement) {
+            None                                     => (0.,     vec![(variable.clone(),  1.)]),
+            Some(Replacement::Shifted { lower, .. }) => (*lower, vec![(variable.clone(),  1.)]),
+            Some(Replacement::Reflected { upper })   => (*upper, vec![(variable.clone(), -1.)]),
+            Some(Replacement::Split) => match variable {
+                Variable::Normal { name } => (0., vec![
+                    (variable.clone(), 1.),
+                    (Variable::Negative { name: name.clone() }, -1.),
+                ]),
+                _ => (0., vec![(variable.clone(), 1.)]),
+            },
+        }
+    }
+}
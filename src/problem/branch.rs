@@ -0,0 +1,147 @@
+use std::{collections::HashMap, time::Instant};
+use super::{Problem, Sense};
+use crate::table::{Table, Solution};
+use crate::error::SolveError;
+use crate::options::SolverOptions;
+use crate::components::{scalor::Scalor, variable::{Variable, VariableKind}, range::Range};
+
+
+/// An LP relaxation waiting to be solved
+struct Node {
+    /// The optimal table of the parent's relaxation and the bound to tighten on it, or `None` for the root
+    branch: Option<(Table, Variable, Range)>,
+    /// Optimal value of the parent's relaxation in the sense of maximization,
+    /// which no solution of this node can exceed
+    bound:  Scalor,
+}
+
+impl Problem {
+    /// Whether all the variables are integer, and all the coefficients, right-hand sides and bounds are integral,
+    /// so that Gomory cuts are valid
+    fn is_pure_integer(&self) -> bool {
+        let integral = |v: &Scalor| v.fract() == 0. || v.is_infinite();
+        self.condition.x.iter().all(|variable| self.kinds.get(variable).is_some_and(VariableKind::is_integer))
+            && self.condition.A.nonzero_values().all(integral)
+            && self.condition.b.iter().all(integral)
+            && self.bounds.values().all(|Range { lower, upper }| integral(lower) && integral(upper))
+    }
+
+    /// Solve by depth-first branch-and-bound: solve the LP relaxation of each node,
+    /// and when some integer variable `x` has a fractional value `v` in it,
+    /// branch into two nodes with `x ≤ floor(v)` and `x ≥ ceil(v)`.
+    /// Each node is warm-started from the optimal table of its parent by `Table::tighten_bound`
+    /// and `Table::reoptimize`, instead of solving its relaxation from scratch.
+    /// 
+    /// The returned solution is the best integer one found. Its duals, reduced costs and
+    /// sensitivity are the ones of the LP relaxation where it was found.
+    pub(crate) fn branch_and_bound(self, options: &SolverOptions) -> Result<Solution, SolveError> {
+        let started = Instant::now();
+
+        /* Optimal values are compared in the sense of maximization */
+        let sense_sign = match self.sense {
+            Sense::Maximize =>  1.,
+            Sense::Minimize => -1.,
+        };
+        let gap = |bound: Scalor, value: Scalor| (bound - value) / value.abs().max(1.);
+
+        let cut_rounds = if self.is_pure_integer() {options.cut_rounds} else {0};
+        let Problem { sense, objective_function, condition, mut bounds, kinds } = self;
+        let integers = condition.x.iter()
+            .filter(|variable| kinds.get(variable).is_some_and(VariableKind::is_integer))
+            .cloned().collect::<Vec<_>>();
+        for (variable, kind) in &kinds {
+            if kind == &VariableKind::Binary {
+                let range = bounds.entry(variable.clone()).or_insert(Range::NONNEGATIVE);
+                range.lower = range.lower.max(0.);
+                range.upper = range.upper.min(1.);
+            }
+        }
+
+        let mut root = Some(Problem { sense, objective_function, condition, bounds, kinds: HashMap::new() });
+        let mut nodes = vec![Node { branch: None, bound: Scalor::INFINITY }];
+        let mut incumbent: Option<(Scalor, Solution)> = None;
+        let mut solved = 0;
+        let mut interruption = None;
+
+        while let Some(node) = nodes.pop() {
+            if incumbent.as_ref().is_some_and(|(value, _)| gap(node.bound, *value) <= options.gap_tolerance) {
+                continue
+            }
+            if options.max_nodes.is_some_and(|max_nodes| solved >= max_nodes) {
+                nodes.push(node);
+                interruption = Some(SolveError::NodeLimit { nodes: solved });
+                break
+            }
+            if let Some(time_limit) = options.time_limit {
+                if started.elapsed() >= time_limit {
+                    nodes.push(node);
+                    interruption = Some(SolveError::TimeLimit { elapsed: started.elapsed() });
+                    break
+                }
+            }
+
+            let relaxation_options = SolverOptions {
+                time_limit: options.time_limit.map(|limit| limit.saturating_sub(started.elapsed())),
+                ..*options
+            };
+            let cut_rounds = if solved == 0 {cut_rounds} else {0};
+            solved += 1;
+            let solved = match node.branch {
+                None => Table::solve_problem(root.take().unwrap(/* only the root has no branch */), &relaxation_options, cut_rounds),
+                Some((mut table, variable, range)) => {
+                    table.tighten_bound(variable, range)?;
+                    table.reoptimize(&relaxation_options).map(|solution| (table, solution))
+                }
+            };
+            let (table, solution) = match solved {
+                Ok(solved) => solved,
+                Err(SolveError::Infeasible) => continue,
+                Err(SolveError::TimeLimit { .. }) => {
+                    nodes.push(Node { branch: None, bound: node.bound });
+                    interruption = Some(SolveError::TimeLimit { elapsed: started.elapsed() });
+                    break
+                }
+                Err(error) => return Err(error),
+            };
+
+            let value = sense_sign * solution.optimal_value;
+            if incumbent.as_ref().is_some_and(|(incumbent_value, _)| gap(value, *incumbent_value) <= options.gap_tolerance) {
+                continue
+            }
+
+            /* Branch on the most fractional integer variable */
+            let fractional = integers.iter()
+                .map(|variable| (variable, solution.variables[variable]))
+                .map(|(variable, v)| (variable, v, (v - v.round()).abs()))
+                .filter(|(_, _, fraction)| *fraction > options.integrality_tolerance)
+                .fold(None, |most: Option<(&Variable, Scalor, Scalor)>, candidate| match most {
+                    Some(most) if most.2 >= candidate.2 => Some(most),
+                    _ => Some(candidate),
+                });
+            match fractional {
+                None => incumbent = Some((value, solution)),
+                Some((variable, v, _)) => {
+                    let down = Range { lower: -Scalor::INFINITY, upper: v.floor() };
+                    let up   = Range { lower: v.ceil(), upper: Scalor::INFINITY };
+
+                    nodes.push(Node { branch: Some((table.clone(), variable.clone(), down)), bound: value });
+                    nodes.push(Node { branch: Some((table,         variable.clone(), up)),   bound: value });
+                }
+            }
+        }
+
+        let Some((value, mut solution)) = incumbent else {
+            return Err(interruption.unwrap_or(SolveError::Infeasible))
+        };
+        for variable in &integers {
+            if let Some(v) = solution.variables.get_mut(variable) {
+                *v = v.round()
+            }
+        }
+        let best_bound = nodes.iter()
+            .map(|node| node.bound)
+            .fold(value, Scalor::max);
+        solution.mip_gap = Some(gap(best_bound, value).max(0.));
+        Ok(solution)
+    }
+}
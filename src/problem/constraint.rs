@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use crate::components::{polynomial::Polynomial, term::Term, sparse::SparseMatrix, scalor::Scalor, variable::Variable};
+use super::{Condition, Sign};
+
+
+/// A row of `Condition` written as `polynomial (sign) rhs`, or by the methods of `Polynomial` and `Term`:
+/// ```
+/// # use simplex_method::{var, Condition, Constraint};
+/// let (x1, x2) = (var("x1"), var("x2"));
+/// let condition = Condition::from_constraints([
+///     (1*&x1 - 0.5*&x2).ge(-1),
+///     (4*&x2).eq(6),
+///     (2*x1 + 5*x2).le(20),
+/// ]);
+/// assert_eq!(condition, Condition::from_constraints([
+///     Constraint::ge(1*var("x1") - 0.5*var("x2"), -1),
+///     Constraint::eq(4*var("x2"), 6),
+///     Constraint::le(2*var("x1") + 5*var("x2"), 20),
+/// ]));
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Constraint {
+    pub(crate) lhs:  Polynomial,
+    pub(crate) sign: Sign,
+    pub(crate) rhs:  Scalor,
+}
+
+impl Constraint {
+    /// Move the constant of `lhs` to `rhs`
+    fn new(mut lhs: Polynomial, sign: Sign, rhs: Scalor) -> Self {
+        let constant = std::mem::take(&mut lhs.constant);
+        Self { lhs, sign, rhs: rhs - constant }
+    }
+
+    /// `lhs ≤ rhs`
+    pub fn le(lhs: impl Into<Polynomial>, rhs: impl Into<Scalor>) -> Self {
+        Self::new(lhs.into(), Sign::LE, rhs.into())
+    }
+    /// `lhs ≥ rhs`
+    pub fn ge(lhs: impl Into<Polynomial>, rhs: impl Into<Scalor>) -> Self {
+        Self::new(lhs.into(), Sign::GE, rhs.into())
+    }
+    /// `lhs = rhs`
+    pub fn eq(lhs: impl Into<Polynomial>, rhs: impl Into<Scalor>) -> Self {
+        Self::new(lhs.into(), Sign::EQ, rhs.into())
+    }
+}
+
+impl Polynomial {
+    /// `self ≤ rhs`
+    pub fn le(self, rhs: impl Into<Scalor>) -> Constraint {
+        Constraint::le(self, rhs)
+    }
+    /// `self ≥ rhs`
+    pub fn ge(self, rhs: impl Into<Scalor>) -> Constraint {
+        Constraint::ge(self, rhs)
+    }
+    /// `self = rhs`
+    pub fn eq(self, rhs: impl Into<Scalor>) -> Constraint {
+        Constraint::eq(self, rhs)
+    }
+}
+impl Term {
+    /// `self ≤ rhs`
+    pub fn le(self, rhs: impl Into<Scalor>) -> Constraint {
+        Constraint::le(self, rhs)
+    }
+    /// `self ≥ rhs`
+    pub fn ge(self, rhs: impl Into<Scalor>) -> Constraint {
+        Constraint::ge(self, rhs)
+    }
+    /// `self = rhs`
+    pub fn eq(self, rhs: impl Into<Scalor>) -> Constraint {
+        Constraint::eq(self, rhs)
+    }
+}
+
+impl Condition {
+    /// Assemble constraints into a `Condition`.
+    /// Columns are the variables in the order they first appear in `constraints`.
+    pub fn from_constraints(constraints: impl IntoIterator<Item = Constraint>) -> Self {
+        let constraints = constraints.into_iter().collect::<Vec<_>>();
+
+        let mut x = Vec::<Variable>::new();
+        for Constraint { lhs, .. } in &constraints {
+            for term in &lhs.terms {
+                if !x.contains(&term.variable) {x.push(term.variable.clone())}
+            }
+        }
+
+        Self::assemble(x, constraints)
+    }
+
+    /// Assemble `constraints` into a `Condition` whose columns are `x`, which must contain all the variables in `constraints`
+    pub(crate) fn assemble(x: Vec<Variable>, constraints: Vec<Constraint>) -> Self {
+        let columns = x.iter().enumerate().map(|(j, variable)| (variable, j)).collect::<HashMap<_, _>>();
+        let (mut triplets, mut signs, mut b) = (Vec::new(), Vec::with_capacity(constraints.len()), Vec::with_capacity(constraints.len()));
+        for (i, Constraint { lhs, sign, rhs }) in constraints.into_iter().enumerate() {
+            for Term { coefficient, variable } in lhs.terms {
+                triplets.push((i, columns[&variable], coefficient));
+            }
+            signs.push(sign);
+            b.push(rhs);
+        }
+
+        let A = SparseMatrix::from_triplets(x.len(), b.len(), triplets).unwrap(/* all indices are in `x` and `constraints` */);
+        Self { A, x, signs, b, names: Vec::new() }
+    }
+}
+
+impl FromIterator<Constraint> for Condition {
+    fn from_iter<I: IntoIterator<Item = Constraint>>(constraints: I) -> Self {
+        Self::from_constraints(constraints)
+    }
+}
@@ -0,0 +1,440 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use super::{Problem, Condition, Constraint, Sense, Sign};
+use crate::error::FormatError;
+use crate::components::{polynomial::Polynomial, term::Term, scalor::Scalor, variable::{Variable, VariableKind}, range::Range};
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token<'t> {
+    Number(Scalor),
+    Name(&'t str),
+    Sign(Sign),
+    Plus,
+    Minus,
+    Colon,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section { Objective, Constraints, Bounds, General, Binary, End }
+
+struct Spanned<'t> {
+    token:  Token<'t>,
+    line:   usize,
+    column: usize,
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || "!\"#$%&()/,.;?@_`'{}|~".contains(c)
+}
+
+/// Split `text` of the `line` into tokens, skipping a comment from `\`
+fn tokenize<'t>(line: usize, text: &'t str, tokens: &mut Vec<Spanned<'t>>) -> Result<(), FormatError> {
+    let text = text.split('\\').next().unwrap();
+    let mut chars = text.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        let column = start + 1;
+        let token = match c {
+            _ if c.is_whitespace() => {chars.next(); continue}
+            '+' => {chars.next(); Token::Plus}
+            '-' => {chars.next(); Token::Minus}
+            ':' => {chars.next(); Token::Colon}
+            '<' | '>' | '=' => {
+                chars.next();
+                let second = chars.next_if(|&(_, c)| matches!(c, '<' | '>' | '='));
+                match (c, second.map(|(_, c)| c)) {
+                    ('<', None | Some('=')) | ('=', Some('<')) => Token::Sign(Sign::LE),
+                    ('>', None | Some('=')) | ('=', Some('>')) => Token::Sign(Sign::GE),
+                    ('=', None | Some('='))                    => Token::Sign(Sign::EQ),
+                    _ => return Err(FormatError::syntax(line, column, format!("Unknown operator `{}`", &text[start..start + 2]))),
+                }
+            }
+            '0'..='9' | '.' => {
+                let mut end = start;
+                while let Some((i, c)) = chars.next_if(|&(_, c)| c.is_ascii_digit() || c == '.') {end = i + c.len_utf8()}
+                /* Exponent only when followed by digits, so that `2e` is `2 e` */
+                let rest = &text[end..];
+                if let Some(exponent) = rest.strip_prefix(['e', 'E']) {
+                    let digits_start = usize::from(exponent.starts_with(['+', '-']));
+                    let digits = exponent[digits_start..].chars().take_while(char::is_ascii_digit).count();
+                    if digits > 0 {
+                        end += 1 + digits_start + digits;
+                        while chars.next_if(|&(i, _)| i < end).is_some() {}
+                    }
+                }
+                let number = &text[start..end];
+                Token::Number(number.parse().map_err(|_| FormatError::syntax(line, column, format!("Invalid number `{number}`")))?)
+            }
+            _ if is_name_char(c) => {
+                let mut end = start;
+                while let Some((i, c)) = chars.next_if(|&(_, c)| is_name_char(c)) {end = i + c.len_utf8()}
+                Token::Name(&text[start..end])
+            }
+            _ => return Err(FormatError::syntax(line, column, format!("Unexpected character `{c}`"))),
+        };
+        tokens.push(Spanned { token, line, column });
+    }
+    Ok(())
+}
+
+struct Parser<'t> {
+    tokens:   Vec<Spanned<'t>>,
+    position: usize,
+    // The last position for errors at the end of input
+    end:      (usize, usize),
+}
+
+impl<'t> Parser<'t> {
+    fn peek(&self) -> Option<Token<'t>> {
+        self.tokens.get(self.position).map(|spanned| spanned.token)
+    }
+    fn peek_second(&self) -> Option<Token<'t>> {
+        self.tokens.get(self.position + 1).map(|spanned| spanned.token)
+    }
+    fn error(&self, message: impl Into<String>) -> FormatError {
+        let (line, column) = self.tokens.get(self.position)
+            .map_or(self.end, |spanned| (spanned.line, spanned.column));
+        FormatError::syntax(line, column, message)
+    }
+
+    /// Consume a section keyword at the current position if any
+    fn section(&mut self) -> Option<(Section, Option<Sense>)> {
+        let Some(Token::Name(word)) = self.peek() else {return None};
+        let (section, words) = match &*word.to_ascii_lowercase() {
+            "maximize" | "maximum" | "max" => ((Section::Objective, Some(Sense::Maximize)), 1),
+            "minimize" | "minimum" | "min" => ((Section::Objective, Some(Sense::Minimize)), 1),
+            "st" | "s.t." | "st." => ((Section::Constraints, None), 1),
+            "subject" | "such" => {
+                let Some(Token::Name(second)) = self.peek_second() else {return None};
+                if !matches!(&*second.to_ascii_lowercase(), "to" | "that") {return None}
+                ((Section::Constraints, None), 2)
+            }
+            "bounds" | "bound" => ((Section::Bounds, None), 1),
+            "general" | "generals" | "gen" | "integer" | "integers" => ((Section::General, None), 1),
+            "binary" | "binaries" | "bin" => ((Section::Binary, None), 1),
+            "end" => ((Section::End, None), 1),
+            _ => return None,
+        };
+        self.position += words;
+        Some(section)
+    }
+    fn at_section(&mut self) -> bool {
+        let position = self.position;
+        let found = self.section().is_some();
+        self.position = position;
+        found
+    }
+
+    /// Consume `name:` if any
+    fn label(&mut self) -> Option<&'t str> {
+        let (Some(Token::Name(name)), Some(Token::Colon)) = (self.peek(), self.peek_second()) else {return None};
+        self.position += 2;
+        Some(name)
+    }
+
+    /// `[+|-] number`, where the number can be `inf` or `infinity`
+    fn number(&mut self) -> Result<Scalor, FormatError> {
+        let mut sign = 1.;
+        while let Some(token @ (Token::Plus | Token::Minus)) = self.peek() {
+            if token == Token::Minus {sign = -sign}
+            self.position += 1;
+        }
+        match self.peek() {
+            Some(Token::Number(number)) => {self.position += 1; Ok(sign * number)}
+            Some(Token::Name(name)) if matches!(&*name.to_ascii_lowercase(), "inf" | "infinity") => {
+                self.position += 1;
+                Ok(sign * Scalor::INFINITY)
+            }
+            _ => Err(self.error("Expected a number")),
+        }
+    }
+
+    /// Terms like `3 x1 - x2 + 4` until a token that can't continue the expression
+    fn expression(&mut self) -> Result<Polynomial, FormatError> {
+        let mut polynomial = Polynomial::new();
+        let mut first = true;
+        loop {
+            let mut sign = 1.;
+            let mut signed = false;
+            while let Some(token @ (Token::Plus | Token::Minus)) = self.peek() {
+                if token == Token::Minus {sign = -sign}
+                signed = true;
+                self.position += 1;
+            }
+            if !first && !signed {return Ok(polynomial)}
+
+            let coefficient = match self.peek() {
+                Some(Token::Number(number)) => {self.position += 1; Some(number)}
+                _ => None,
+            };
+            let name = match self.peek() {
+                Some(Token::Name(name)) if !self.at_section() => {self.position += 1; Some(name)}
+                _ => None,
+            };
+            match (coefficient, name) {
+                (_, Some(name)) => polynomial += Term { coefficient: sign * coefficient.unwrap_or(1.), variable: Variable::Normal { name: name.to_string() } },
+                (Some(constant), None) => polynomial = polynomial + sign * constant,
+                (None, None) if first && !signed => return Ok(polynomial),
+                (None, None) => return Err(self.error("Expected a term")),
+            }
+            first = false;
+        }
+    }
+
+    fn variable(&mut self) -> Result<Variable, FormatError> {
+        match self.peek() {
+            Some(Token::Name(name)) if !self.at_section() => {
+                self.position += 1;
+                Ok(Variable::Normal { name: name.to_string() })
+            }
+            _ => Err(self.error("Expected a variable")),
+        }
+    }
+
+    fn sign(&mut self) -> Result<Sign, FormatError> {
+        match self.peek() {
+            Some(Token::Sign(sign)) => {self.position += 1; Ok(sign)}
+            _ => Err(self.error("Expected `<=`, `>=` or `=`")),
+        }
+    }
+}
+
+impl Problem {
+    /// Read a problem in CPLEX LP format:
+    /// ```text
+    /// \ comment
+    /// Maximize
+    ///  obj: 3 x1 + 2 x2
+    /// Subject To
+    ///  c1: x1 + x2 <= 4
+    /// Bounds
+    ///  x1 <= 3
+    ///  -1 <= x2 <= 1
+    /// General
+    ///  x1
+    /// End
+    /// ```
+    /// Section keywords ( `Maximize`, `Minimize`, `Subject To`, `Bounds`, `General`, `Binary`, `End` and their variants )
+    /// are case-insensitive and can't be variable names. When some constraints are named,
+    /// the others are named `R1`, `R2`, ... by their positions.
+    /// A negative upper bound makes the lower bound -∞ unless the variable has a lower bound, as `from_mps` does.
+    pub fn from_lp(reader: impl BufRead) -> Result<Problem, FormatError> {
+        let lines = reader.lines().collect::<Result<Vec<_>, _>>()?;
+        let mut tokens = Vec::new();
+        for (n, text) in lines.iter().enumerate() {
+            tokenize(n + 1, text, &mut tokens)?;
+        }
+        let end = lines.last().map_or((1, 1), |text| (lines.len(), text.len() + 1));
+        let mut parser = Parser { tokens, position: 0, end };
+
+        let Some((Section::Objective, Some(sense))) = parser.section() else {
+            return Err(parser.error("Expected `Maximize` or `Minimize`"))
+        };
+        parser.label();
+        let objective_function = parser.expression()?;
+
+        let mut constraints = Vec::new();
+        let mut labels = Vec::new();
+        let mut bounds = HashMap::<Variable, Range>::new();
+        let mut kinds = HashMap::new();
+        let mut declared = Vec::new();  // Variables in `Bounds`, `General` and `Binary`
+        let mut lower_given = Vec::<Variable>::new();
+        loop {
+            let Some((section, _)) = parser.section() else {
+                return Err(parser.error(match parser.peek() {
+                    None => "Expected `End`",
+                    Some(_) => "Expected a section",
+                }))
+            };
+            match section {
+                Section::Objective => return Err(parser.error("Duplicated objective function")),
+                Section::End => break,
+                Section::Constraints => while parser.peek().is_some() && !parser.at_section() {
+                    labels.push(parser.label());
+                    let lhs = parser.expression()?;
+                    let sign = parser.sign()?;
+                    let rhs = parser.number()?;
+                    constraints.push(match sign {
+                        Sign::LE => lhs.le(rhs),
+                        Sign::GE => lhs.ge(rhs),
+                        Sign::EQ => lhs.eq(rhs),
+                    });
+                }
+                Section::Bounds => while parser.peek().is_some() && !parser.at_section() {
+                    /* `x free`, `x (sign) number`, or `number (sign) x [(sign) number]` */
+                    if matches!(parser.peek(), Some(Token::Name(name)) if !matches!(&*name.to_ascii_lowercase(), "inf" | "infinity")) {
+                        let variable = parser.variable()?;
+                        let range = bounds.entry(variable.clone()).or_insert(Range::NONNEGATIVE);
+                        if matches!(parser.peek(), Some(Token::Name(word)) if word.eq_ignore_ascii_case("free")) {
+                            parser.position += 1;
+                            *range = Range::FREE;
+                        } else {
+                            let sign = parser.sign()?;
+                            let value = parser.number()?;
+                            match sign {
+                                Sign::LE => range.set_upper(value, lower_given.contains(&variable)),
+                                Sign::GE => {range.lower = value; lower_given.push(variable.clone())}
+                                Sign::EQ => {*range = Range { lower: value, upper: value }; lower_given.push(variable.clone())}
+                            }
+                        }
+                        declared.push(variable);
+                        continue
+                    }
+                    let value = parser.number()?;
+                    let sign = parser.sign()?;
+                    let variable = parser.variable()?;
+                    let range = bounds.entry(variable.clone()).or_insert(Range::NONNEGATIVE);
+                    /* `value (sign) x` is `x (reversed sign) value` */
+                    match sign {
+                        Sign::LE => {range.lower = value; lower_given.push(variable.clone())}
+                        Sign::GE => range.set_upper(value, lower_given.contains(&variable)),
+                        Sign::EQ => {*range = Range { lower: value, upper: value }; lower_given.push(variable.clone())}
+                    }
+                    if let Some(Token::Sign(sign)) = parser.peek() {
+                        parser.position += 1;
+                        let value = parser.number()?;
+                        match sign {
+                            Sign::LE => range.set_upper(value, lower_given.contains(&variable)),
+                            Sign::GE => {range.lower = value; lower_given.push(variable.clone())}
+                            Sign::EQ => return Err(parser.error("Unexpected `=` in a double bound")),
+                        }
+                    }
+                    declared.push(variable);
+                }
+                Section::General | Section::Binary => while parser.peek().is_some() && !parser.at_section() {
+                    let variable = parser.variable()?;
+                    kinds.insert(variable.clone(), match section {
+                        Section::General => VariableKind::Integer,
+                        _                => VariableKind::Binary,
+                    });
+                    declared.push(variable);
+                }
+            }
+        }
+        if parser.peek().is_some() {
+            return Err(parser.error("Unexpected token after `End`"))
+        }
+
+        /* Columns are the variables in the order they first appear */
+        let mut x = Vec::<Variable>::new();
+        let appearances = objective_function.terms.iter()
+            .chain(constraints.iter().flat_map(|constraint: &Constraint| &constraint.lhs.terms))
+            .map(|term| &term.variable)
+            .chain(&declared);
+        for variable in appearances {
+            if !x.contains(variable) {x.push(variable.clone())}
+        }
+
+        let names = match labels.iter().any(Option::is_some) {
+            true  => labels.iter().enumerate().map(|(i, label)| label.map_or_else(|| format!("R{}", i + 1), str::to_string)).collect(),
+            false => Vec::new(),
+        };
+
+        Ok(Problem {
+            sense,
+            objective_function,
+            condition: Condition::assemble(x, constraints).with_names(names),
+            bounds: bounds.into_iter().filter(|(_, range)| range != &Range::NONNEGATIVE).collect(),
+            kinds,
+        })
+    }
+
+    /// Write the problem in CPLEX LP format. Constraints are labeled when named by `Condition::with_names`.
+    ///
+    /// Names that are not read back as themselves ( e.g. `steel-42`, `product 7`, `2x` or `bounds` )
+    /// make `io::ErrorKind::InvalidInput` before writing anything.
+    pub fn write_lp(&self, mut writer: impl Write) -> io::Result<()> {
+        let Problem { sense, objective_function, condition, bounds, kinds } = self;
+
+        let names = condition.x.iter().map(|variable| ("variable", format!("{variable:?}")))
+            .chain(condition.names.iter().map(|name| ("constraint", name.clone())));
+        for (kind, name) in names {
+            if !is_valid_name(&name) {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("The {kind} name `{name}` can't be written in LP format")))
+            }
+        }
+
+        writeln!(writer, "{}", match sense {Sense::Maximize => "Maximize", Sense::Minimize => "Minimize"})?;
+        write!(writer, " obj:")?;
+        write_expression(&mut writer, objective_function.terms.iter().map(|term| (term.coefficient, &term.variable)))?;
+        match objective_function.constant {
+            constant if constant > 0. => write!(writer, " + {constant}")?,
+            constant if constant < 0. => write!(writer, " - {}", -constant)?,
+            _ if objective_function.terms.is_empty() => write!(writer, " 0")?,
+            _ => (),
+        }
+        writeln!(writer)?;
+
+        writeln!(writer, "Subject To")?;
+        let mut rows = vec![Vec::new(); condition.b.len()];
+        for (j, variable) in condition.x.iter().enumerate() {
+            for (i, coefficient) in condition.A.column(j).unwrap(/* `j` is less than `row_size` */) {
+                rows[i].push((coefficient, variable))
+            }
+        }
+        for (i, ((row, sign), rhs)) in rows.into_iter().zip(&condition.signs).zip(&condition.b).enumerate() {
+            if let Some(name) = condition.names.get(i) {write!(writer, " {name}:")?}
+            if row.is_empty() {write!(writer, " 0")?}
+            write_expression(&mut writer, row.into_iter())?;
+            writeln!(writer, " {} {rhs}", match sign {Sign::LE => "<=", Sign::GE => ">=", Sign::EQ => "="})?;
+        }
+
+        writeln!(writer, "Bounds")?;
+        for variable in &condition.x {
+            if kinds.get(variable) == Some(&VariableKind::Binary) {continue}
+            let Some(&Range { lower, upper }) = bounds.get(variable) else {continue};
+            match (lower, upper) {
+                _ if lower == upper => writeln!(writer, " {variable:?} = {lower}")?,
+                _ if lower == -Scalor::INFINITY && upper == Scalor::INFINITY => writeln!(writer, " {variable:?} free")?,
+                (_, Scalor::INFINITY)  => writeln!(writer, " {variable:?} >= {}", format_bound(lower))?,
+                (0., _) if upper >= 0. => writeln!(writer, " {variable:?} <= {}", format_bound(upper))?,
+                _                      => writeln!(writer, " {} <= {variable:?} <= {}", format_bound(lower), format_bound(upper))?,
+            }
+        }
+
+        for (header, kind) in [("General", VariableKind::Integer), ("Binary", VariableKind::Binary)] {
+            let variables = condition.x.iter().filter(|variable| kinds.get(*variable) == Some(&kind)).collect::<Vec<_>>();
+            if variables.is_empty() {continue}
+            writeln!(writer, "{header}")?;
+            for variable in variables {
+                writeln!(writer, " {variable:?}")?;
+            }
+        }
+
+        writeln!(writer, "End")
+    }
+}
+
+/// Whether `name` is read as a name token of itself, that is not a keyword
+fn is_valid_name(name: &str) -> bool {
+    let mut tokens = Vec::new();
+    if tokenize(1, name, &mut tokens).is_err() {return false}
+    if !matches!(&*tokens, [Spanned { token: Token::Name(token), .. }] if *token == name) {return false}
+    let mut parser = Parser { tokens, position: 0, end: (1, 1) };
+    !parser.at_section() && !matches!(&*name.to_ascii_lowercase(), "inf" | "infinity" | "free" | "subject" | "such")
+}
+
+fn format_bound(value: Scalor) -> String {
+    match value {
+        Scalor::INFINITY  => "+inf".to_string(),
+        value if value == -Scalor::INFINITY => "-inf".to_string(),
+        value => format!("{value}"),
+    }
+}
+
+/// ` 3 x1 - x2 + 2.5 x3`
+fn write_expression<'v>(writer: &mut impl Write, terms: impl Iterator<Item = (Scalor, &'v Variable)>) -> io::Result<()> {
+    for (k, (coefficient, variable)) in terms.enumerate() {
+        let operator = match (k, coefficient < 0.) {
+            (0, false) => "",
+            (0, true)  => " -",
+            (_, false) => " +",
+            (_, true)  => " -",
+        };
+        match coefficient.abs() {
+            1.                 => write!(writer, "{operator} {variable:?}")?,
+            absolute           => write!(writer, "{operator} {absolute} {variable:?}")?,
+        }
+    }
+    Ok(())
+}
@@ -1,30 +1,54 @@
 #![allow(non_snake_case)]
 #[cfg(test)] mod test;
+mod bounds;
+mod branch;
+mod constraint;
+mod lp;
+mod mps;
+pub use constraint::Constraint;
+pub(crate) use bounds::Substitution;
 
-use crate::components::{polynomial::Polynomial, matrix::Matrix, scalor::Scalor, variable::Variable};
+use std::collections::HashMap;
+use crate::components::{polynomial::Polynomial, sparse::SparseMatrix, scalor::Scalor, variable::{Variable, VariableKind}, range::Range};
+use crate::error::SolveError;
 
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Problem {
+    pub(crate) sense:              Sense,
     pub(crate) objective_function: Polynomial,
     pub(crate) condition:          Condition,
+    #[cfg_attr(feature = "serde", serde(with = "crate::components::variable::map"))]
+    pub(crate) bounds:             HashMap<Variable, Range>,         // Variables not here are `0 ≤ x`
+    #[cfg_attr(feature = "serde", serde(with = "crate::components::variable::map"))]
+    pub(crate) kinds:              HashMap<Variable, VariableKind>,  // Variables not here are continuous
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Sense { Maximize, Minimize }
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Condition {
-    pub(crate) A:    Matrix<Scalor>,
-    pub(crate) x:    Vec<Variable>,
-    pub(crate) sign: Sign,
-    pub(crate) b:    Vec<Scalor>,
+    pub(crate) A:     SparseMatrix,
+    pub(crate) x:     Vec<Variable>,
+    pub(crate) signs: Vec<Sign>,  // This's index is just the row number of `A`
+    pub(crate) b:     Vec<Scalor>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub(crate) names: Vec<String>,  // Empty, or the name of each row
 }
 
-#[derive(PartialEq)]
-pub(crate) enum Sign { EQ, LE }
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Sign { EQ, LE, GE }
 impl std::fmt::Debug for Sign {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(match self {
             Self::EQ => "=",
             Self::LE => "≤",
+            Self::GE => "≥",
         })
     }
 }
@@ -33,45 +57,179 @@ impl Problem {
     pub(crate) fn into_standard_form(self) -> Problem {
         if self.is_standard_form() {return self}
 
-        let Problem { objective_function, condition } = self;
-        Problem { objective_function, condition:condition.into_standard_form() }
+        let Problem { sense, objective_function, condition, bounds, kinds } = self;
+        Problem { sense, objective_function, condition:condition.into_standard_form(), bounds, kinds }
     }
 
     fn is_standard_form(&self) -> bool {
-        matches!(self.condition.sign, Sign::EQ)
+        self.condition.signs.iter().all(|sign| matches!(sign, Sign::EQ))
+    }
+
+    /// Check the sizes of `condition`, that all its columns are normal variables,
+    /// and that every variable of the objective function, `bounds` and `kinds` is its column.
+    /// Solving checks this first, but a problem read from a file can be checked before solving.
+    pub fn validate(&self) -> Result<(), SolveError> {
+        self.condition.validate()?;
+
+        if let Some(variable) = self.condition.x.iter().find(|variable| !variable.is_normal()) {
+            return Err(SolveError::NotNormalColumn { variable: variable.clone() })
+        }
+
+        let used_variables = self.objective_function.terms.iter().map(|term| &term.variable)
+            .chain(self.bounds.keys())
+            .chain(self.kinds.keys());
+        for variable in used_variables {
+            if !self.condition.x.contains(variable) {
+                return Err(SolveError::UnknownVariable { variable: variable.clone() })
+            }
+        }
+        Ok(())
+    }
+
+    /// Add an empty column of `variable` unless it's already a column
+    fn add_column(&mut self, variable: &Variable) {
+        if self.condition.x.contains(variable) {return}
+        self.condition.A.push_column([]).unwrap(/* no entry */);
+        self.condition.x.push(variable.clone());
     }
 }
 impl Condition {
+    /// Check that `x`, `signs` and `b` fit the size of `A`
+    pub(crate) fn validate(&self) -> Result<(), SolveError> {
+        for (name, expected, found) in [
+            ("x",     self.A.row_size,    self.x.len()),
+            ("signs", self.A.column_size, self.signs.len()),
+            ("b",     self.A.column_size, self.b.len()),
+        ] {
+            if expected != found {
+                return Err(SolveError::DimensionMismatch { name, expected, found })
+            }
+        }
+        if !self.names.is_empty() && self.names.len() != self.b.len() {
+            return Err(SolveError::DimensionMismatch { name: "names", expected: self.b.len(), found: self.names.len() })
+        }
+        Ok(())
+    }
+
+    /// Add a slack variable `s_i` to each `≤` row and a surplus variable `-s_i` to each `≥` row.
+    /// `=` rows get no additional variable.
+    /// 
+    /// Each additional column is stored as its only nonzero entry, not as a part of a dense identity matrix.
     pub(crate) fn into_standard_form(self) -> Condition {
-        let Condition { A, x,  b, .. } = self;
+        let Condition { mut A, mut x, signs, b, names } = self;
 
-        let slack_variables = (1..=b.len()).into_iter()
-            .map(|i| Variable::Slack { id: i }).collect::<Vec<Variable>>();
+        for (i, sign) in signs.iter().enumerate() {
+            let (variable, coefficient) = match sign {
+                Sign::LE => (Variable::Slack   { id: i + 1 },  1.),
+                Sign::GE => (Variable::Surplus { id: i + 1 }, -1.),
+                Sign::EQ => continue,
+            };
+            A.push_column([(i, coefficient)]).unwrap(/* `i` is less than `A.column_size` */);
+            x.push(variable);
+        }
 
         Condition {
-            A: A.try_concat(Matrix::identity(b.len())).unwrap(/* `b.len()` equals to `A.column_size` */),
-            x: [x, slack_variables].concat(),
-            sign: Sign::EQ,
-            b
+            A, x,
+            signs: vec![Sign::EQ; b.len()],
+            b,
+            names,
         }
     }
 }
 
 impl Problem {
+    /// Variables only in `objective_function` are added to the columns of `condition`
     pub fn maximize(objective_function: impl Into<Polynomial>, condition: Condition) -> Self {
-        Self {
-            objective_function: objective_function.into(),
+        Self::new(Sense::Maximize, objective_function.into(), condition)
+    }
+
+    /// Variables only in `objective_function` are added to the columns of `condition`
+    pub fn minimize(objective_function: impl Into<Polynomial>, condition: Condition) -> Self {
+        Self::new(Sense::Minimize, objective_function.into(), condition)
+    }
+
+    fn new(sense: Sense, objective_function: Polynomial, condition: Condition) -> Self {
+        let mut problem = Self {
+            sense,
+            objective_function,
             condition,
+            bounds: HashMap::new(),
+            kinds:  HashMap::new(),
+        };
+        for term in problem.objective_function.terms.clone() {
+            problem.add_column(&term.variable);
         }
+        problem
+    }
+
+    /// Variables of the problem in the column order of its `Condition`
+    pub fn variables(&self) -> &[Variable] {
+        &self.condition.x
+    }
+
+    /// Names of the constraints, in the row order of its `Condition`. Empty when they are not named.
+    pub fn constraint_names(&self) -> &[String] {
+        &self.condition.names
+    }
+
+    /// `lower ≤ variable ≤ upper` instead of `0 ≤ variable`. Either of them can be infinite.
+    /// `variable` is added to the columns if it's not in the constraints.
+    pub fn bound(mut self, variable: Variable, lower: impl Into<Scalor>, upper: impl Into<Scalor>) -> Self {
+        self.add_column(&variable);
+        self.bounds.insert(variable, Range { lower: lower.into(), upper: upper.into() });
+        self
+    }
+
+    /// `variable` can take any value
+    pub fn free(self, variable: Variable) -> Self {
+        self.bound(variable, -Scalor::INFINITY, Scalor::INFINITY)
+    }
+
+    /// `variable` can take only integer values in its bounds. Then `solve` runs branch-and-bound.
+    pub fn integer(mut self, variable: Variable) -> Self {
+        self.add_column(&variable);
+        self.kinds.insert(variable, VariableKind::Integer);
+        self
+    }
+
+    /// `variable` can take only 0 or 1. Then `solve` runs branch-and-bound.
+    pub fn binary(mut self, variable: Variable) -> Self {
+        self.add_column(&variable);
+        self.kinds.insert(variable, VariableKind::Binary);
+        self
     }
 }
 impl Condition {
-    /// `\forall i, Ax_i ≤ b_i`
-    pub fn each_le(A: Matrix<Scalor>, x: Vec<Variable>, b: Vec<impl Into<Scalor>>) -> Self {
+    /// `\forall i, Ax_i (signs_i) b_i`
+    pub fn new(A: impl Into<SparseMatrix>, x: Vec<Variable>, signs: Vec<Sign>, b: Vec<impl Into<Scalor>>) -> Self {
         Self {
-            A, x,
-            sign: Sign::LE,
+            A: A.into(), x, signs,
             b:b.into_iter().map(Into::into).collect(),
+            names: Vec::new(),
         }
     }
+
+    /// Name the rows in order, as MPS and LP formats do
+    pub fn with_names(mut self, names: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.names = names.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// `\forall i, Ax_i ≤ b_i`
+    pub fn each_le(A: impl Into<SparseMatrix>, x: Vec<Variable>, b: Vec<impl Into<Scalor>>) -> Self {
+        let signs = vec![Sign::LE; b.len()];
+        Self::new(A, x, signs, b)
+    }
+
+    /// `\forall i, Ax_i ≥ b_i`
+    pub fn each_ge(A: impl Into<SparseMatrix>, x: Vec<Variable>, b: Vec<impl Into<Scalor>>) -> Self {
+        let signs = vec![Sign::GE; b.len()];
+        Self::new(A, x, signs, b)
+    }
+
+    /// `\forall i, Ax_i = b_i`
+    pub fn each_eq(A: impl Into<SparseMatrix>, x: Vec<Variable>, b: Vec<impl Into<Scalor>>) -> Self {
+        let signs = vec![Sign::EQ; b.len()];
+        Self::new(A, x, signs, b)
+    }
 }
@@ -0,0 +1,417 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use super::{Problem, Condition, Sense, Sign};
+use crate::error::FormatError;
+use crate::components::{polynomial::Polynomial, term::Term, sparse::SparseMatrix, scalor::Scalor, variable::{Variable, VariableKind}, range::Range};
+
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section { Name, ObjSense, Rows, Columns, Rhs, Ranges, Bounds }
+
+#[derive(Clone, Copy, PartialEq)]
+enum RowType { N, L, G, E }
+
+/// Tokens of a data line with their columns ( starting from 1 )
+struct Fields<'l> {
+    line:   usize,
+    tokens: Vec<(usize, &'l str)>,
+}
+
+impl<'l> Fields<'l> {
+    /// Split by whitespaces as free MPS, or cut at the fixed columns as fixed MPS
+    /// when the number of the tokens is invalid for `section` ( e.g. names contain spaces )
+    fn new(line: usize, text: &'l str, section: Section) -> Self {
+        let tokens = text.char_indices()
+            .filter(|&(i, c)| !c.is_whitespace() && (i == 0 || text[..i].ends_with(char::is_whitespace)))
+            .map(|(i, _)| (i + 1, text[i..].split_whitespace().next().unwrap()))
+            .collect::<Vec<_>>();
+        if Self::is_valid(section, tokens.len()) {
+            return Self { line, tokens }
+        }
+
+        let tokens = [(1, 3), (4, 12), (14, 22), (24, 36), (39, 47), (49, 61)].into_iter()
+            .filter_map(|(start, end)| {
+                let field = text.get(start..end.min(text.len()))?;
+                let trimmed = field.trim();
+                (!trimmed.is_empty()).then(|| (start + 1 + (field.len() - field.trim_start().len()), trimmed))
+            }).collect();
+        Self { line, tokens }
+    }
+
+    fn is_valid(section: Section, count: usize) -> bool {
+        match section {
+            Section::Name | Section::ObjSense => count >= 1,
+            Section::Rows                     => count == 2,
+            Section::Columns                  => count == 3 || count == 5,
+            Section::Rhs | Section::Ranges    => (2..=5).contains(&count),
+            Section::Bounds                   => (2..=4).contains(&count),
+        }
+    }
+
+    fn check(&self, section: Section) -> Result<(), FormatError> {
+        if Self::is_valid(section, self.tokens.len()) {return Ok(())}
+        Err(FormatError::syntax(self.line, self.tokens.first().map_or(1, |(column, _)| *column), "Unexpected number of fields"))
+    }
+
+    fn number(&self, index: usize) -> Result<Scalor, FormatError> {
+        let (column, token) = self.tokens[index];
+        token.parse().map_err(|_| FormatError::syntax(self.line, column, format!("Expected a number, found `{token}`")))
+    }
+
+    /// `(name, value)` pairs after the first `skip` tokens
+    fn pairs(&self, skip: usize) -> Result<Vec<(usize, &'l str, Scalor)>, FormatError> {
+        (skip..self.tokens.len()).step_by(2)
+            .map(|k| Ok((self.tokens[k].0, self.tokens[k].1, self.number(k + 1)?)))
+            .collect()
+    }
+}
+
+impl Problem {
+    /// Read a problem in fixed or free MPS format, with `ROWS`, `COLUMNS`, `RHS`, `RANGES` and `BOUNDS` sections
+    /// ( and `OBJSENSE` section, where `MAX` or `MAXIMIZE` makes the problem maximizing: minimizing by default ).
+    ///
+    /// The first `N` row is the objective function, and the other `N` rows are ignored.
+    /// Each ranged row becomes two constraints: the one of its own type in its position,
+    /// and the other one named `{row}_range` after all the rows. Columns between `'INTORG'` and `'INTEND'` markers are integer variables.
+    /// A negative `UP` bound makes the lower bound -∞ unless the column has a lower bound, as `from_lp` does.
+    pub fn from_mps(reader: impl BufRead) -> Result<Problem, FormatError> {
+        let mut section = None;
+        let mut sense = Sense::Minimize;
+
+        let mut objective_row = None::<String>;
+        let mut row_types = Vec::<RowType>::new();
+        let mut row_names = Vec::<String>::new();
+        let mut row_indices = HashMap::<String, usize>::new();  // `usize::MAX` for free rows
+
+        let mut columns = Vec::<Variable>::new();
+        let mut column_indices = HashMap::<String, usize>::new();
+        let mut triplets = Vec::new();
+        let mut objective = Vec::<(usize, Scalor)>::new();
+        let mut kinds = HashMap::new();
+        let mut in_integer_markers = false;
+
+        let mut rhs = Vec::<Scalor>::new();
+        let mut constant = 0.;
+        let mut ranges = Vec::<Option<Scalor>>::new();
+        let mut bounds = HashMap::<usize, Range>::new();
+        let mut lower_given = Vec::<usize>::new();
+
+        for (n, text) in reader.lines().enumerate() {
+            let (text, line) = (text?, n + 1);
+            if text.trim().is_empty() || text.starts_with('*') {continue}
+
+            if !text.starts_with(char::is_whitespace) {
+                let mut tokens = text.split_whitespace();
+                section = Some(match tokens.next().unwrap() {
+                    "NAME"     => Section::Name,
+                    "ROWS"     => Section::Rows,
+                    "COLUMNS"  => Section::Columns,
+                    "RHS"      => Section::Rhs,
+                    "RANGES"   => Section::Ranges,
+                    "BOUNDS"   => Section::Bounds,
+                    "OBJSENSE" => match tokens.next() {
+                        Some(value) => {sense = parse_sense(line, text.find(value).unwrap() + 1, value)?; Section::Name}
+                        None        => Section::ObjSense,
+                    },
+                    "ENDATA"   => break,
+                    header     => return Err(FormatError::syntax(line, 1, format!("Unknown section `{header}`"))),
+                });
+                continue
+            }
+
+            let Some(current) = section else {
+                return Err(FormatError::syntax(line, 1, "Data line before any section"))
+            };
+            let fields = Fields::new(line, &text, current);
+            fields.check(current)?;
+            let row_index = |(column, name): (usize, &str)| row_indices.get(name).copied()
+                .ok_or_else(|| FormatError::syntax(line, column, format!("Unknown row `{name}`")));
+
+            match current {
+                Section::Name => (),
+                Section::ObjSense => {
+                    let (column, value) = fields.tokens[0];
+                    sense = parse_sense(line, column, value)?;
+                }
+                Section::Rows => {
+                    let ((column, row_type), (_, name)) = (fields.tokens[0], fields.tokens[1]);
+                    let row_type = match row_type {
+                        "N" => RowType::N, "L" => RowType::L, "G" => RowType::G, "E" => RowType::E,
+                        _ => return Err(FormatError::syntax(line, column, format!("Unknown row type `{row_type}`"))),
+                    };
+                    if row_type == RowType::N {
+                        row_indices.insert(name.to_string(), usize::MAX);
+                        if objective_row.is_none() {objective_row = Some(name.to_string())}
+                    } else {
+                        row_indices.insert(name.to_string(), row_types.len());
+                        row_types.push(row_type);
+                        row_names.push(name.to_string());
+                        rhs.push(0.);
+                        ranges.push(None);
+                    }
+                }
+                Section::Columns => {
+                    if fields.tokens[1].1 == "'MARKER'" {
+                        let (column, marker) = fields.tokens[2];
+                        in_integer_markers = match marker {
+                            "'INTORG'" => true,
+                            "'INTEND'" => false,
+                            _ => return Err(FormatError::syntax(line, column, format!("Unknown marker `{marker}`"))),
+                        };
+                        continue
+                    }
+
+                    let name = fields.tokens[0].1;
+                    let j = *column_indices.entry(name.to_string()).or_insert_with(|| {
+                        columns.push(Variable::Normal { name: name.to_string() });
+                        columns.len() - 1
+                    });
+                    if in_integer_markers {
+                        kinds.insert(columns[j].clone(), VariableKind::Integer);
+                    }
+                    for (column, row, value) in fields.pairs(1)? {
+                        if objective_row.as_deref() == Some(row) {
+                            objective.push((j, value))
+                        } else {
+                            let i = row_index((column, row))?;
+                            if i != usize::MAX {triplets.push((i, j, value))}
+                        }
+                    }
+                }
+                Section::Rhs | Section::Ranges => {
+                    let skip = fields.tokens.len() % 2;  // The set name is omitted when even
+                    for (column, row, value) in fields.pairs(skip)? {
+                        if objective_row.as_deref() == Some(row) {
+                            if current == Section::Rhs {constant = -value}
+                            continue
+                        }
+                        let i = row_index((column, row))?;
+                        if i == usize::MAX {continue}
+                        match current {
+                            Section::Rhs => rhs[i]    = value,
+                            _            => ranges[i] = Some(value),
+                        }
+                    }
+                }
+                Section::Bounds => {
+                    let (column, bound_type) = fields.tokens[0];
+                    let needs_value = !matches!(bound_type, "FR" | "MI" | "PL" | "BV");
+                    let (name, value) = match (fields.tokens.len(), needs_value) {
+                        (4, _) | (3, true) => (fields.tokens[fields.tokens.len() - 2], Some(fields.number(fields.tokens.len() - 1)?)),
+                        (3, false)         => (fields.tokens[2], None),
+                        (2, false)         => (fields.tokens[1], None),
+                        _ => return Err(FormatError::syntax(line, column, format!("Bound type `{bound_type}` needs a value"))),
+                    };
+                    let j = *column_indices.get(name.1)
+                        .ok_or_else(|| FormatError::syntax(line, name.0, format!("Unknown column `{}`", name.1)))?;
+
+                    let range = bounds.entry(j).or_insert(Range::NONNEGATIVE);
+                    let value = value.unwrap_or_default();
+                    match bound_type {
+                        "UP" | "UI" => range.set_upper(value, lower_given.contains(&j)),
+                        "LO" | "LI" => {range.lower = value; lower_given.push(j)}
+                        "FX"        => {range.lower = value; range.upper = value; lower_given.push(j)}
+                        "FR"        => *range = Range::FREE,
+                        "MI"        => {range.lower = -Scalor::INFINITY; lower_given.push(j)}
+                        "PL"        => range.upper = Scalor::INFINITY,
+                        "BV"        => {kinds.insert(columns[j].clone(), VariableKind::Binary);}
+                        _ => return Err(FormatError::syntax(line, column, format!("Unknown bound type `{bound_type}`"))),
+                    }
+                    if matches!(bound_type, "UI" | "LI") {
+                        kinds.insert(columns[j].clone(), VariableKind::Integer);
+                    }
+                }
+            }
+        }
+
+        /* Ranged rows */
+        let mut signs = Vec::with_capacity(row_types.len());
+        let mut additional_rows = Vec::new();
+        for (i, (row_type, range)) in row_types.iter().zip(&ranges).enumerate() {
+            let (sign, other) = match (row_type, range) {
+                (RowType::L, None)                  => (Sign::LE, None),
+                (RowType::G, None)                  => (Sign::GE, None),
+                (RowType::E, None)                  => (Sign::EQ, None),
+                (RowType::L, Some(r))               => (Sign::LE, Some((Sign::GE, rhs[i] - r.abs()))),
+                (RowType::G, Some(r))               => (Sign::GE, Some((Sign::LE, rhs[i] + r.abs()))),
+                (RowType::E, Some(r)) if *r > 0.    => (Sign::GE, Some((Sign::LE, rhs[i] + r))),
+                (RowType::E, Some(r)) if *r < 0.    => (Sign::LE, Some((Sign::GE, rhs[i] + r))),
+                (RowType::E, Some(_))               => (Sign::EQ, None),
+                (RowType::N, _)                     => unreachable!(),
+            };
+            signs.push(sign);
+            if let Some(other) = other {additional_rows.push((i, other))}
+        }
+        let m = row_types.len();
+        for (k, (i, (sign, value))) in additional_rows.into_iter().enumerate() {
+            triplets.extend(
+                triplets.iter()
+                    .filter(|(row, ..)| *row == i)
+                    .map(|&(_, j, a)| (m + k, j, a))
+                    .collect::<Vec<_>>()
+            );
+            signs.push(sign);
+            rhs.push(value);
+            row_names.push(format!("{}_range", row_names[i]));
+        }
+
+        let mut objective_coefficients = vec![0.; columns.len()];
+        for (j, value) in objective {
+            objective_coefficients[j] += value
+        }
+        let objective_function = Polynomial {
+            terms: columns.iter().zip(objective_coefficients)
+                .filter(|(_, coefficient)| *coefficient != 0.)
+                .map(|(variable, coefficient)| Term { coefficient, variable: variable.clone() })
+                .collect(),
+            constant,
+        };
+
+        Ok(Problem {
+            sense,
+            objective_function,
+            condition: Condition {
+                A: SparseMatrix::from_triplets(columns.len(), rhs.len(), triplets).unwrap(/* indices are of `columns` and `rhs` */),
+                b: rhs,
+                x: columns.clone(),
+                signs,
+                names: row_names,
+            },
+            bounds: bounds.into_iter()
+                .filter(|(_, range)| range != &Range::NONNEGATIVE)
+                .map(|(j, range)| (columns[j].clone(), range))
+                .collect(),
+            kinds,
+        })
+    }
+
+    /// Write the problem in MPS format. Fields are at the columns of fixed MPS
+    /// while names are within 8 characters, and anyway separated by spaces as free MPS.
+    ///
+    /// Rows are named by `Condition::with_names`, or `R1`, `R2`, ... when not named,
+    /// and the objective function is `OBJ` ( with `_`s appended while a row has the name ).
+    /// Names that are empty, contain whitespaces or are duplicated make `io::ErrorKind::InvalidInput` before writing anything.
+    pub fn write_mps(&self, mut writer: impl Write) -> io::Result<()> {
+        let Problem { sense, objective_function, condition, bounds, kinds } = self;
+        let name = |variable: &Variable| format!("{variable:?}");
+
+        let rows = match &*condition.names {
+            []    => (1..=condition.b.len()).map(|i| format!("R{i}")).collect(),
+            names => names.to_vec(),
+        };
+        let mut objective_row = String::from("OBJ");
+        while rows.contains(&objective_row) {objective_row.push('_')}
+        check_names(rows.iter().cloned(), "row")?;
+        check_names(condition.x.iter().map(name), "column")?;
+        if let Some(row) = rows.iter().find(|row| *row == "'MARKER'") {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("Row name `{row}` is the integer marker of MPS format")))
+        }
+
+        writeln!(writer, "NAME")?;
+        if *sense == Sense::Maximize {
+            writeln!(writer, "OBJSENSE")?;
+            writeln!(writer, "    MAX")?;
+        }
+
+        writeln!(writer, "ROWS")?;
+        writeln!(writer, " N  {objective_row}")?;
+        for (row, sign) in rows.iter().zip(&condition.signs) {
+            let row_type = match sign {Sign::LE => "L", Sign::GE => "G", Sign::EQ => "E"};
+            writeln!(writer, " {row_type}  {row}")?;
+        }
+
+        writeln!(writer, "COLUMNS")?;
+        let mut in_integer_markers = false;
+        for (j, variable) in condition.x.iter().enumerate() {
+            let is_integer = kinds.get(variable) == Some(&VariableKind::Integer);
+            if is_integer != in_integer_markers {
+                let marker = if is_integer {"'INTORG'"} else {"'INTEND'"};
+                writeln!(writer, "    MARKER                 'MARKER'                 {marker}")?;
+                in_integer_markers = is_integer;
+            }
+
+            let objective = objective_function.terms.iter()
+                .find(|term| &term.variable == variable)
+                .map(|term| term.coefficient);
+            let mut entries = objective.map(|c| (&objective_row, c)).into_iter()
+                .chain(condition.A.column(j).unwrap().map(|(i, a)| (&rows[i], a)))
+                .peekable();
+            if entries.peek().is_none() {
+                write_field(&mut writer, "", &name(variable), &objective_row, 0.)?;
+            }
+            for (row, value) in entries {
+                write_field(&mut writer, "", &name(variable), row, value)?;
+            }
+        }
+        if in_integer_markers {
+            writeln!(writer, "    MARKER                 'MARKER'                 'INTEND'")?;
+        }
+
+        writeln!(writer, "RHS")?;
+        if objective_function.constant != 0. {
+            write_field(&mut writer, "", "RHS", &objective_row, -objective_function.constant)?;
+        }
+        for (row, value) in rows.iter().zip(&condition.b).filter(|(_, value)| **value != 0.) {
+            write_field(&mut writer, "", "RHS", row, *value)?;
+        }
+
+        writeln!(writer, "BOUNDS")?;
+        for variable in &condition.x {
+            if kinds.get(variable) == Some(&VariableKind::Binary) {
+                writeln!(writer, " BV BND       {}", name(variable))?;
+                continue
+            }
+            let Some(&Range { lower, upper }) = bounds.get(variable) else {continue};
+            if lower == upper {
+                write_field(&mut writer, "FX", "BND", &name(variable), lower)?;
+                continue
+            }
+            match (lower.is_finite(), upper.is_finite()) {
+                (false, false) => writeln!(writer, " FR BND       {}", name(variable))?,
+                (false, true)  => writeln!(writer, " MI BND       {}", name(variable))?,
+                (true, _) if lower != 0. || upper < 0. => write_field(&mut writer, "LO", "BND", &name(variable), lower)?,
+                _ => (),
+            }
+            if upper.is_finite() {
+                write_field(&mut writer, "UP", "BND", &name(variable), upper)?;
+            }
+        }
+
+        writeln!(writer, "ENDATA")
+    }
+}
+
+fn parse_sense(line: usize, column: usize, value: &str) -> Result<Sense, FormatError> {
+    match value {
+        "MAX" | "MAXIMIZE" => Ok(Sense::Maximize),
+        "MIN" | "MINIMIZE" => Ok(Sense::Minimize),
+        _ => Err(FormatError::syntax(line, column, format!("Unknown objective sense `{value}`"))),
+    }
+}
+
+/// Names are separated by whitespaces, and each of them must be unique in its kind
+fn check_names(names: impl Iterator<Item = String>, kind: &str) -> io::Result<()> {
+    let mut seen = std::collections::HashSet::new();
+    for name in names {
+        let problem = if name.is_empty() {
+            "is empty"
+        } else if name.contains(char::is_whitespace) {
+            "contains whitespaces"
+        } else if !seen.insert(name.clone()) {
+            "is duplicated"
+        } else {
+            continue
+        };
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("The {kind} name `{name}` {problem}")))
+    }
+    Ok(())
+}
+
+/// Write a line of fields at the columns 2-3, 5-12, 15-22 and 25-36
+fn write_field(writer: &mut impl Write, code: &str, name: &str, row: &str, value: Scalor) -> io::Result<()> {
+    let value = match format!("{value}") {
+        short if short.len() <= 12 => short,
+        _ => format!("{value:e}"),
+    };
+    writeln!(writer, " {code:<2} {name:<8}  {row:<8}  {value:>12}")
+}
@@ -1,4 +1,5 @@
-use crate::{*, components::variable::slack};
+use std::collections::HashMap;
+use crate::{*, components::{scalor::Scalor, polynomial::Polynomial, variable::{slack, surplus, Variable}}};
 
 
 #[test] fn test_into_standard_form() {
@@ -24,14 +25,17 @@ use crate::{*, components::variable::slack};
         )
     );
     assert_eq!(problem.into_standard_form(), Problem {
+        sense: Sense::Maximize,
         objective_function: 4*var("x1") + 5*var("x2"),
-        condition: Condition::each_le(
-            matrix! {
+        bounds: HashMap::new(),
+        kinds: HashMap::new(),
+        condition: Condition {
+            A: matrix! {
                 2, 5, 1, 0, 0
                 6, 4, 0, 1, 0
                 3, 2, 0, 0, 1
-            },
-            vec![
+            }.into(),
+            x: vec![
                 var("x1"),
                 var("x2"),
                 slack(1),
@@ -39,13 +43,719 @@ use crate::{*, components::variable::slack};
                 slack(3),
             ],
 
+            signs: vec![Sign::EQ; 3],
+
+            b: vec![
+                20.,
+                27.,
+                12.,
+            ],
+            names: Vec::new(),
+        }
+    });
+}
+
+#[test] fn test_into_standard_form_with_signs() {
+    let problem = Problem::maximize(3*var("x1") + 2*var("x2"),
+        Condition::new(
+            matrix! {
+                1,  1
+                1,  3
+                1, -1
+            },
+            vec![
+                var("x1"),
+                var("x2"),
+            ],
+            vec![
+                Sign::LE,
+                Sign::GE,
+                Sign::EQ,
+            ],
+            vec![
+                4,
+                6,
+                0,
+            ]
+        )
+    );
+    assert_eq!(problem.into_standard_form(), Problem {
+        sense: Sense::Maximize,
+        objective_function: 3*var("x1") + 2*var("x2"),
+        bounds: HashMap::new(),
+        kinds: HashMap::new(),
+        condition: Condition {
+            A: matrix! {
+                1,  1, 1,  0
+                1,  3, 0, -1
+                1, -1, 0,  0
+            }.into(),
+            x: vec![
+                var("x1"),
+                var("x2"),
+                slack(1),
+                surplus(2),
+            ],
+
+            signs: vec![Sign::EQ; 3],
+
+            b: vec![
+                4.,
+                6.,
+                0.,
+            ],
+            names: Vec::new(),
+        }
+    });
+}
+
+#[test] fn test_substitute_bounds() {
+    let problem = Problem::maximize(3*var("x1") + 2*var("x2") + 1*var("x3"),
+        Condition::each_le(
+            matrix! {
+                1, 1, 1
+                2, 0, 1
+            },
+            vec![
+                var("x1"),
+                var("x2"),
+                var("x3"),
+            ],
+
             /* less than or equals to */
 
             vec![
-                20,
-                27,
-                12,
+                4,
+                6,
             ]
         )
+    )
+    .bound(var("x1"), 1, 3)
+    .bound(var("x2"), -Scalor::INFINITY, 2)
+    .free(var("x3"));
+
+    let (problem, _) = problem.substitute_bounds();
+    assert_eq!(problem, Problem {
+        sense: Sense::Maximize,
+        objective_function: 3*var("x1") - 2*var("x2") + 1*var("x3") - 1*Variable::Negative { name: "x3".to_string() },
+        bounds: HashMap::new(),
+        kinds: HashMap::new(),
+        condition: Condition {
+            A: matrix! {
+                1, -1, 1, -1
+                2,  0, 1, -1
+                1,  0, 0,  0
+            }.into(),
+            x: vec![
+                var("x1"),
+                var("x2"),
+                var("x3"),
+                Variable::Negative { name: "x3".to_string() },
+            ],
+
+            signs: vec![Sign::LE; 3],
+
+            b: vec![
+                1.,
+                4.,
+                2.,
+            ],
+            names: Vec::new(),
+        }
     });
 }
+
+#[test] fn test_bounded_variables() {
+    let solution = Problem::maximize(3*var("x1") + 2*var("x2"),
+        Condition::each_le(
+            matrix! {
+                1, 1
+            },
+            vec![
+                var("x1"),
+                var("x2"),
+            ],
+
+            /* less than or equals to */
+
+            vec![
+                4,
+            ]
+        )
+    )
+    .bound(var("x1"), 1, 3)
+    .bound(var("x2"), 0, 10)
+    .solve().unwrap();
+    assert_eq!(solution.variables, HashMap::from([
+        (var("x1"), 3.),
+        (var("x2"), 1.),
+    ]));
+    assert_eq!(solution.optimal_value, 11.);
+    assert_eq!(solution.duals, vec![2.]);
+    assert_eq!(solution.reduced_costs, HashMap::from([
+        (var("x1"), 1.),
+        (var("x2"), 0.),
+    ]));
+    assert_eq!(solution.activities, vec![4.]);
+
+    let solution = Problem::maximize(1*var("x1") + 1*var("x2"),
+        Condition::each_le(
+            matrix! {
+                1, 2
+            },
+            vec![
+                var("x1"),
+                var("x2"),
+            ],
+
+            /* less than or equals to */
+
+            vec![
+                4,
+            ]
+        )
+    )
+    .bound(var("x1"), -Scalor::INFINITY, 2)
+    .solve().unwrap();
+    assert_eq!(solution.variables, HashMap::from([
+        (var("x1"), 2.),
+        (var("x2"), 1.),
+    ]));
+    assert_eq!(solution.optimal_value, 3.);
+    assert_eq!(solution.reduced_costs[&var("x1")], 0.5);
+}
+
+#[test] fn test_free_variables() {
+    let solution = Problem::minimize(2*var("x") + 1*var("y"),
+        Condition::new(
+            matrix! {
+                 1, 1
+                -1, 1
+            },
+            vec![
+                var("x"),
+                var("y"),
+            ],
+            vec![
+                Sign::GE,
+                Sign::LE,
+            ],
+            vec![
+                1,
+                5,
+            ]
+        )
+    )
+    .free(var("x"))
+    .solve().unwrap();
+    assert_eq!(solution.variables, HashMap::from([
+        (var("x"), -2.),
+        (var("y"),  3.),
+    ]));
+    assert_eq!(solution.optimal_value, -1.);
+}
+
+#[test] fn test_variables_out_of_constraints() {
+    let (x, y) = (var("x"), var("y"));
+
+    /* `y` only in the objective function */
+    let problem = Problem::maximize(1*&x + 1*&y, Condition::from_constraints([
+        Constraint::le(1*&x, 4),
+    ]));
+    assert_eq!(problem.variables(), [var("x"), var("y")]);
+    assert_eq!(problem.clone().solve(), Err(SolveError::Unbounded));
+
+    /* `y` bounded only by `bound` */
+    let solution = problem.bound(y.clone(), 0, 3).solve().unwrap();
+    assert_eq!(solution.variables, HashMap::from([
+        (var("x"), 4.),
+        (var("y"), 3.),
+    ]));
+    assert_eq!(solution.optimal_value, 7.);
+
+    /* no constraints */
+    let solution = Problem::maximize(1*&x, Condition::from_constraints([]))
+        .bound(x.clone(), 0, 5)
+        .solve().unwrap();
+    assert_eq!(solution.variables, HashMap::from([
+        (var("x"), 5.),
+    ]));
+    assert_eq!(solution.optimal_value, 5.);
+
+    let problem = Problem {
+        bounds: HashMap::from([(var("z"), Range { lower: 0., upper: 1. })]),
+        ..Problem::maximize(1*&x, Condition::from_constraints([]))
+    };
+    assert_eq!(problem.solve(), Err(SolveError::UnknownVariable { variable: var("z") }));
+
+    let problem = Problem::maximize(1*&x, Condition::from_constraints([
+        Constraint::le(1*&x + 1*slack(1), 5),
+    ]));
+    assert_eq!(problem.validate(), Err(SolveError::NotNormalColumn { variable: slack(1) }));
+    assert_eq!(problem.solve(), Err(SolveError::NotNormalColumn { variable: slack(1) }));
+}
+
+#[test] fn test_condition_from_constraints() {
+    let (x1, x2, x3) = (var("x1"), var("x2"), var("x3"));
+    let condition = Condition::from_constraints([
+        Constraint::le(2*&x1 + 5*&x2, 20),
+        Constraint::ge(1*&x3 - 1*&x1 + 3*&x1, -1.5),
+        Constraint::eq(4*&x2, 6),
+    ]);
+    assert_eq!(condition, Condition::new(
+        matrix! {
+            2, 5, 0
+            2, 0, 1
+            0, 4, 0
+        },
+        vec![
+            var("x1"),
+            var("x2"),
+            var("x3"),
+        ],
+        vec![
+            Sign::LE,
+            Sign::GE,
+            Sign::EQ,
+        ],
+        vec![
+            20.,
+            -1.5,
+            6.,
+        ]
+    ));
+
+    let solution = Problem::maximize(4*&x1 + 5*&x2, [
+        Constraint::le(2*&x1 + 5*&x2, 20),
+        Constraint::le(6*&x1 + 4*&x2, 27),
+        Constraint::le(3*&x1 + 1*&x2, 12),
+    ].into_iter().collect()).solve().unwrap();
+    assert_eq!(solution.variables, HashMap::from([
+        (var("x1"), 2.5),
+        (var("x2"), 3.),
+    ]));
+    assert_eq!(solution.optimal_value, 25.);
+}
+
+#[test] fn test_polynomial() {
+    let (x, y) = (var("x"), var("y"));
+    assert_eq!(1*&x + 2*&y + 3*&x, 4*&x + 2*&y);
+    assert_eq!(1*&x + 2*&y - 1*&x, Polynomial::from(2*&y));
+    assert_eq!((1*&x + 1*&y) + (2*&x - 1*&y + 3), 3*&x + 3);
+    let polynomial: Polynomial = 1*&x - 2*&y + 1;
+    assert_eq!(-polynomial * 2, -2*&x + 4*&y - 2);
+    assert_eq!([1*&x, 2*&y, 3*&x].into_iter().sum::<Polynomial>(), 4*&x + 2*&y);
+    assert_eq!([1*&x + 1, 1*&y + 2].into_iter().sum::<Polynomial>(), 1*&x + 1*&y + 3);
+
+    let constraint = Constraint::le(1*&x + 1*&y + 2, 6);
+    assert_eq!(constraint, Constraint::le(1*&x + 1*&y, 4));
+    assert_eq!((2.0_f64*&x + 1_i32*&y).ge(1), Constraint::ge(2*&x + 1*&y, 1));
+    assert_eq!((2*&x + 0.5*&y).eq(1), Constraint::eq(2*&x + 0.5*&y, 1));
+    assert_eq!(
+        Scalor::from(2u32)*var("x") + Scalor::from(3u8)*&y - Scalor::from(1i16)*&x + Scalor::from(0.5f32)*&y,
+        1*&x + 3.5*&y
+    );
+
+    let solution = Problem::maximize(3*&x + 2*&y + 10, [
+        Constraint::le(1*&x + 1*&y, 4),
+        Constraint::le(1*&x + 3*&y + 1*&x, 6),
+    ].into_iter().collect()).solve().unwrap();
+    assert_eq!(solution.variables, HashMap::from([
+        (var("x"), 3.),
+        (var("y"), 0.),
+    ]));
+    assert_eq!(solution.optimal_value, 19.);
+}
+
+#[test] fn test_branch_and_bound() {
+    let (x, y) = (var("x"), var("y"));
+    let problem = Problem::maximize(5*&x + 4*&y, [
+        Constraint::le(6*&x + 4*&y, 24),
+        Constraint::le(1*&x + 2*&y, 6),
+    ].into_iter().collect())
+    .integer(x.clone())
+    .integer(y.clone());
+
+    let solution = problem.clone().solve().unwrap();
+    assert_eq!(solution.variables, HashMap::from([
+        (var("x"), 4.),
+        (var("y"), 0.),
+    ]));
+    assert_eq!(solution.optimal_value, 20.);
+    assert_eq!(solution.mip_gap, Some(0.));
+
+    assert_eq!(
+        problem.solve_with(SolverOptions { max_nodes: Some(1), ..Default::default() }),
+        Err(SolveError::NodeLimit { nodes: 1 })
+    );
+
+    let (a, b, c) = (var("a"), var("b"), var("c"));
+    let solution = Problem::maximize(10*&a + 13*&b + 7*&c, [
+        Constraint::le(3*&a + 4*&b + 2*&c, 6),
+    ].into_iter().collect())
+    .binary(a.clone())
+    .binary(b.clone())
+    .binary(c.clone())
+    .solve().unwrap();
+    assert_eq!(solution.variables, HashMap::from([
+        (var("a"), 0.),
+        (var("b"), 1.),
+        (var("c"), 1.),
+    ]));
+    assert_eq!(solution.optimal_value, 20.);
+
+    let solution = Problem::minimize(1*&x + 1*&y, [
+        Constraint::ge(2*&x + 2*&y, 3),
+    ].into_iter().collect())
+    .integer(x.clone())
+    .solve().unwrap();
+    assert_eq!(solution.optimal_value, 1.5);
+    let solution = Problem::minimize(1*&x + 1*&y, [
+        Constraint::ge(2*&x + 2*&y, 3),
+    ].into_iter().collect())
+    .integer(x.clone())
+    .integer(y.clone())
+    .solve().unwrap();
+    assert_eq!(solution.optimal_value, 2.);
+
+    assert_eq!(
+        Problem::maximize(1*&x, [Constraint::eq(2*&x, 1)].into_iter().collect())
+            .integer(x.clone())
+            .solve(),
+        Err(SolveError::Infeasible)
+    );
+
+    /* Warm-started nodes over shifted and reflected integer variables, against enumeration */
+    let (a, b, c) = (var("a"), var("b"), var("c"));
+    let solution = Problem::maximize(7*&a + 5*&b - 3*&c, [
+        Constraint::le(2*&a + 3*&b - 1*&c, 12.5),
+        Constraint::ge(1*&a - 1*&b + 2*&c, -1.5),
+        Constraint::le(3*&a + 1*&b, 14.2),
+    ].into_iter().collect())
+    .bound(a.clone(), -2, 5)
+    .bound(b.clone(), 0, 6)
+    .bound(c.clone(), -Scalor::INFINITY, 4)
+    .integer(a.clone())
+    .integer(b.clone())
+    .integer(c.clone())
+    .solve().unwrap();
+    let expected = (-2..=5).flat_map(|a| (0..=6).flat_map(move |b| (-20..=4).map(move |c| (a, b, c))))
+        .map(|(a, b, c)| (a as Scalor, b as Scalor, c as Scalor))
+        .filter(|(a, b, c)| 2.*a + 3.*b - c <= 12.5 && a - b + 2.*c >= -1.5 && 3.*a + b <= 14.2)
+        .map(|(a, b, c)| 7.*a + 5.*b - 3.*c)
+        .fold(Scalor::NEG_INFINITY, Scalor::max);
+    assert!((solution.optimal_value - expected).abs() < 1e-9, "{} != {expected}", solution.optimal_value);
+}
+
+#[test] fn test_gomory_cuts() {
+    let (x1, x2) = (var("x1"), var("x2"));
+    let problem = Problem::maximize(1*&x2, [
+        Constraint::le( 3*&x1 + 2*&x2, 6),
+        Constraint::le(-3*&x1 + 2*&x2, 0),
+    ].into_iter().collect())
+    .integer(x1.clone())
+    .integer(x2.clone());
+
+    assert_eq!(
+        problem.clone().solve_with(SolverOptions { max_nodes: Some(1), ..Default::default() }),
+        Err(SolveError::NodeLimit { nodes: 1 })
+    );
+
+    let solution = problem.solve_with(SolverOptions { cut_rounds: 10, max_nodes: Some(1), ..Default::default() }).unwrap();
+    assert_eq!(solution.variables, HashMap::from([
+        (var("x1"), 1.),
+        (var("x2"), 1.),
+    ]));
+    assert_eq!(solution.optimal_value, 1.);
+    assert_eq!(solution.mip_gap, Some(0.));
+
+    let solution = Problem::maximize(5*&x1 + 4*&x2, [
+        Constraint::le(6*&x1 + 4*&x2, 24),
+        Constraint::le(1*&x1 + 2*&x2, 6),
+    ].into_iter().collect())
+    .integer(x1.clone())
+    .integer(x2.clone())
+    .solve_with(SolverOptions { cut_rounds: 20, ..Default::default() }).unwrap();
+    assert_eq!(solution.variables, HashMap::from([
+        (var("x1"), 4.),
+        (var("x2"), 0.),
+    ]));
+    assert!((solution.optimal_value - 20.).abs() < 1e-9);
+}
+
+#[test] fn test_mps() {
+    let mps = "\
+* maximize 3 x1 + 2 x2 + 4 x3 - 1 ( RHS of the objective is the negated constant )
+NAME          SAMPLE
+OBJSENSE
+    MAX
+ROWS
+ N  COST
+ L  LIM1
+ G  LIM2
+ E  MYEQN
+COLUMNS
+    MARKER                 'MARKER'                 'INTORG'
+    X1        COST         3   LIM1         1
+    X1        LIM2         1
+    MARKER                 'MARKER'                 'INTEND'
+    X2        COST         2   LIM1         1
+    X3        COST         4   MYEQN        1
+    X3        LIM2         1
+RHS
+    RHS       COST         1
+    RHS       LIM1         4   LIM2         1
+    RHS       MYEQN        7
+RANGES
+    RNG       LIM1         2
+BOUNDS
+ UP BND       X1           4
+ MI BND       X2
+ UP BND       X2           1
+ENDATA
+";
+    let problem = Problem::from_mps(mps.as_bytes()).unwrap();
+    let (x1, x2, x3) = (var("X1"), var("X2"), var("X3"));
+    assert_eq!(problem, Problem::maximize(3*&x1 + 2*&x2 + 4*&x3 - 1, Condition::from_constraints([
+        Constraint::le(1*&x1 + 1*&x2, 4),
+        Constraint::ge(1*&x1 + 1*&x3, 1),
+        Constraint::eq(1*&x3, 7),
+        Constraint::ge(1*&x1 + 1*&x2, 2),
+    ]).with_names(["LIM1", "LIM2", "MYEQN", "LIM1_range"]))
+    .bound(x1.clone(), 0, 4)
+    .bound(x2.clone(), -Scalor::INFINITY, 1)
+    .integer(x1.clone()));
+
+    let mut written = Vec::new();
+    problem.write_mps(&mut written).unwrap();
+    assert_eq!(Problem::from_mps(written.as_slice()).unwrap(), problem);
+
+    let solution = problem.solve().unwrap();
+    assert_eq!(solution.variables, HashMap::from([
+        (var("X1"), 4.),
+        (var("X2"), 0.),
+        (var("X3"), 7.),
+    ]));
+    assert_eq!(solution.optimal_value, 39.);
+
+    /* Names with other characters than alphanumerics */
+    let (steel, scrap) = (var("steel-42"), var("scrap.metal"));
+    let problem = Problem::minimize(2*&steel + 1*&scrap, Condition::from_constraints([
+        Constraint::ge(1*&steel + 1*&scrap, 10),
+        Constraint::le(1*&steel - 1*&scrap, 4),
+    ]).with_names(["demand/week", "OBJ"]));
+    let mut written = Vec::new();
+    problem.write_mps(&mut written).unwrap();
+    let read = Problem::from_mps(written.as_slice()).unwrap();
+    assert_eq!(read, problem);
+    assert_eq!(read.constraint_names(), ["demand/week", "OBJ"]);
+
+    for problem in [
+        Problem::maximize(1*var("product 7"), Condition::from_constraints([])),
+        Problem::maximize(1*&steel, Condition::from_constraints([Constraint::le(1*&steel, 1)]).with_names([""])),
+        Problem::maximize(1*&steel, Condition::from_constraints([Constraint::le(1*&steel, 1), Constraint::le(1*&steel, 2)]).with_names(["c", "c"])),
+    ] {
+        let mut written = Vec::new();
+        assert_eq!(problem.write_mps(&mut written).unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+        assert!(written.is_empty());
+    }
+
+    /* A negative upper bound makes the lower bound -∞ unless it's given, as `test_lp` */
+    let problem = Problem::from_mps("\
+ROWS
+ N  COST
+COLUMNS
+    X1        COST         1
+    X2        COST         1
+    X3        COST         1
+BOUNDS
+ UP BND       X1           -2
+ LO BND       X2           -3
+ UP BND       X2           -1
+ LO BND       X3           0
+ UP BND       X3           -1
+ENDATA
+".as_bytes()).unwrap();
+    assert_eq!(problem.bounds, HashMap::from([
+        (var("X1"), Range { lower: -Scalor::INFINITY, upper: -2. }),
+        (var("X2"), Range { lower: -3., upper: -1. }),
+        (var("X3"), Range { lower: 0., upper: -1. }),
+    ]));
+    let mut written = Vec::new();
+    problem.write_mps(&mut written).unwrap();
+    assert_eq!(Problem::from_mps(written.as_slice()).unwrap(), problem);
+
+    assert!(matches!(
+        Problem::from_mps("ROWS\n N  COST\nCOLUMNS\n    X1        COST         three\n".as_bytes()),
+        Err(FormatError::Syntax { line: 4, column: 28, .. })
+    ));
+    assert!(matches!(
+        Problem::from_mps("ROWS\n N  COST\nCOLUMNS\n    X1        LIM1         1\n".as_bytes()),
+        Err(FormatError::Syntax { line: 4, column: 15, .. })
+    ));
+}
+
+#[test] fn test_lp() {
+    let lp = "\
+\\ The same problem as `test_mps`
+Maximize
+ obj: 3 x1 + 2 x2
+      + 4 x3 - 1
+Subject To
+ lim1: x1 + x2 <= 4
+ lim2: x1 + x3 >= 1
+ myeqn: x3 = 7
+ x1 + x2 >= 2
+Bounds
+ x1 <= 4
+ -inf <= x2 <= 1
+General
+ x1
+End
+";
+    let problem = Problem::from_lp(lp.as_bytes()).unwrap();
+    let (x1, x2, x3) = (var("x1"), var("x2"), var("x3"));
+    assert_eq!(problem, Problem::maximize(3*&x1 + 2*&x2 + 4*&x3 - 1, Condition::from_constraints([
+        Constraint::le(1*&x1 + 1*&x2, 4),
+        Constraint::ge(1*&x1 + 1*&x3, 1),
+        Constraint::eq(1*&x3, 7),
+        Constraint::ge(1*&x1 + 1*&x2, 2),
+    ]).with_names(["lim1", "lim2", "myeqn", "R4"]))
+    .bound(x1.clone(), 0, 4)
+    .bound(x2.clone(), -Scalor::INFINITY, 1)
+    .integer(x1.clone()));
+
+    let mut written = Vec::new();
+    problem.write_lp(&mut written).unwrap();
+    assert_eq!(Problem::from_lp(written.as_slice()).unwrap(), problem);
+
+    let problem = Problem::minimize(-2*&x1 + 0.5*&x2, [
+        Constraint::le(1*&x1 - 1*&x2 + 1*&x3, 1.5),
+    ].into_iter().collect())
+    .free(x2.clone())
+    .bound(x1.clone(), -1, Scalor::INFINITY)
+    .binary(x3.clone());
+    let mut written = Vec::new();
+    problem.write_lp(&mut written).unwrap();
+    assert_eq!(Problem::from_lp(written.as_slice()).unwrap(), problem);
+
+    /* A negative upper bound makes the lower bound -∞ unless it's given, as `test_mps` */
+    let problem = Problem::from_lp("\
+Minimize
+ obj: x1 + x2 + x3 + x4
+Bounds
+ x1 <= -2
+ x2 >= -3
+ x2 <= -1
+ 0 <= x3 <= -1
+ -1 >= x4
+End
+".as_bytes()).unwrap();
+    assert_eq!(problem.bounds, HashMap::from([
+        (var("x1"), Range { lower: -Scalor::INFINITY, upper: -2. }),
+        (var("x2"), Range { lower: -3., upper: -1. }),
+        (var("x3"), Range { lower: 0., upper: -1. }),
+        (var("x4"), Range { lower: -Scalor::INFINITY, upper: -1. }),
+    ]));
+    let mut written = Vec::new();
+    problem.write_lp(&mut written).unwrap();
+    assert_eq!(Problem::from_lp(written.as_slice()).unwrap(), problem);
+
+    /* Names with the other characters of LP format than alphanumerics */
+    let (steel, scrap) = (var("steel_42"), var("scrap.metal#1"));
+    let problem = Problem::minimize(2*&steel + 1*&scrap, Condition::from_constraints([
+        Constraint::ge(1*&steel + 1*&scrap, 10),
+    ]).with_names(["demand/week"]));
+    let mut written = Vec::new();
+    problem.write_lp(&mut written).unwrap();
+    assert_eq!(Problem::from_lp(written.as_slice()).unwrap(), problem);
+
+    for problem in [
+        Problem::maximize(1*var("steel-42"), Condition::from_constraints([])),
+        Problem::maximize(1*var("product 7"), Condition::from_constraints([])),
+        Problem::maximize(1*var("2x"), Condition::from_constraints([])),
+        Problem::maximize(1*var("Bounds"), Condition::from_constraints([])),
+        Problem::maximize(1*&steel, Condition::from_constraints([Constraint::le(1*&steel, 1)]).with_names(["c 1"])),
+    ] {
+        let mut written = Vec::new();
+        assert_eq!(problem.write_lp(&mut written).unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+        assert!(written.is_empty());
+    }
+
+    assert!(matches!(
+        Problem::from_lp("Maximize\n x1\nSubject To\n x1 + <= 4\nEnd\n".as_bytes()),
+        Err(FormatError::Syntax { line: 4, column: 7, .. })
+    ));
+    assert!(matches!(
+        Problem::from_lp("Maximize\n x1\nSubject To\n x1 <= 4\n".as_bytes()),
+        Err(FormatError::Syntax { line: 4, column: 9, .. })
+    ));
+}
+
+#[cfg(feature = "serde")]
+#[test] fn test_serde() {
+    let (x1, x2) = (var("x1"), var("x2"));
+    let problem = Problem::maximize(3*&x1 + 2*&x2 - 1, [
+        Constraint::le(1*&x1 + 1*&x2, 4),
+        Constraint::ge(1*&x1 - 1*&x2, -2),
+    ].into_iter().collect())
+    .bound(x1.clone(), -1, 3)
+    .free(x2.clone())
+    .integer(x1.clone());
+
+    let json = serde_json::to_string(&problem).unwrap();
+    assert_eq!(serde_json::from_str::<Problem>(&json).unwrap(), problem);
+
+    let json = serde_json::to_value(&problem).unwrap();
+    assert_eq!(json["condition"]["A"], serde_json::json!({
+        "row_size": 2, "column_size": 2,
+        "entries": [[0, 0, 1.], [1, 0, 1.], [0, 1, 1.], [1, 1, -1.]],
+    }));
+    assert!(json["bounds"].as_array().unwrap().contains(&serde_json::json!(
+        [{ "Normal": "x2" }, { "lower": null, "upper": null }]
+    )));
+    assert_eq!(json["kinds"], serde_json::json!([[{ "Normal": "x1" }, "Integer"]]));
+
+    for (variable, json) in [
+        (var("x1"),                                serde_json::json!({ "Normal": "x1" })),
+        (var("slack#1"),                           serde_json::json!({ "Normal": "slack#1" })),
+        (Variable::Negative { name: "x2".into() }, serde_json::json!({ "Negative": "x2" })),
+        (slack(1),                                 serde_json::json!({ "Slack": 1 })),
+        (surplus(2),                               serde_json::json!({ "Surplus": 2 })),
+        (Variable::Artificial { id: 3 },           serde_json::json!({ "Artificial": 3 })),
+        (Variable::Object,                         serde_json::json!("Object")),
+    ] {
+        assert_eq!(serde_json::to_value(&variable).unwrap(), json);
+        assert_eq!(serde_json::from_value::<Variable>(json).unwrap(), variable);
+    }
+
+    /* Duplicated terms are merged as `Polynomial`'s operators do */
+    let objective_function = serde_json::from_value::<Polynomial>(serde_json::json!({
+        "terms": [
+            { "coefficient": 1., "variable": { "Normal": "x1" } },
+            { "coefficient": 2., "variable": { "Normal": "x1" } },
+            { "coefficient": 1., "variable": { "Normal": "x2" } },
+            { "coefficient": -1., "variable": { "Normal": "x2" } },
+        ],
+        "constant": 0.,
+    })).unwrap();
+    assert_eq!(objective_function, Polynomial::from(3*&x1));
+    let solution = Problem::maximize(objective_function, Condition::from_constraints([
+        Constraint::le(1*&x1 + 1*&x2, 6),
+    ])).solve().unwrap();
+    assert_eq!(solution.optimal_value, 18.);
+
+    let solution = problem.solve().unwrap();
+    let json = serde_json::to_string(&solution).unwrap();
+    assert_eq!(serde_json::from_str::<Solution>(&json).unwrap(), solution);
+
+    let matrix = matrix! {
+        1, 2
+        3, 4
+    };
+    let json = serde_json::to_string(&matrix).unwrap();
+    assert_eq!(json, "[[1.0,2.0],[3.0,4.0]]");
+    assert_eq!(serde_json::from_str::<Matrix<Scalor>>(&json).unwrap(), matrix);
+    assert!(serde_json::from_str::<Matrix<Scalor>>("[[1.0,2.0],[3.0]]").is_err());
+    assert!(serde_json::from_str::<Matrix<Scalor>>("[]").is_err());
+}
@@ -0,0 +1,40 @@
+use crate::options::SolverOptions;
+use crate::components::scalor::Scalor;
+use super::Table;
+
+
+impl Table {
+    /// Add the Gomory fractional cut made from the row whose structural base variable is the most fractional,
+    /// or return `false` when all structural base variables are integral.
+    /// 
+    /// For a row `x_B + Σ a_j x_j = b` of nonbasic variables `x_j`, with `f(v) = v - floor(v)`,
+    /// every solution whose variables are all integer satisfies `Σ f(a_j) x_j ≥ f(b)`,
+    /// while the current one doesn't because its nonbasic variables are 0.
+    /// So this is valid only when slack and surplus variables are also integer,
+    /// that is, for a pure integer problem whose coefficients are all integral.
+    /// 
+    /// The cut is added as `- Σ f(a_j) x_j + s = - f(b)` with a new slack variable `s` as its base variable,
+    /// which makes the table primal infeasible but keeps it dual feasible.
+    pub(super) fn add_gomory_cut(&mut self, options: &SolverOptions) -> bool {
+        let fraction = |v: Scalor| {
+            let f = v - v.floor();
+            if f <= options.integrality_tolerance || f >= 1. - options.integrality_tolerance {0.} else {f}
+        };
+
+        let Some((row, value)) = (0..(self.bases.len() - 1))
+            .filter(|&i| self.bases[i].variable.is_structural())
+            .map(|i| (i, fraction(self.bases[i].value)))
+            .filter(|(_, f)| *f > 0.)
+            .reduce(|most, (i, f)| if (f - 0.5).abs() < (most.1 - 0.5).abs() {(i, f)} else {most})
+        else {return false};
+
+        let base_columns = self.base_columns();
+        let cut = (0..self.variables.len())
+            .map(|j| if base_columns.contains(&j) || self.variables[j].is_artificial() {0.} else {
+                -fraction(self.coefficients[row][j])
+            }).collect::<Vec<_>>();
+        self.add_row(cut, -value);
+
+        true
+    }
+}
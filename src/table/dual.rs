@@ -0,0 +1,93 @@
+use crate::error::SolveError;
+use crate::options::SolverOptions;
+use super::{Table, Pivot, Progress};
+
+
+impl Table {
+    /// Prepare an initial table for dual simplex method, or return `false` without changing it
+    /// when that's impossible.
+    /// 
+    /// Each row whose base variable is artificial is multiplied by -1 to take
+    /// the surplus variable ( or the slack variable of a flipped row ) as its base variable,
+    /// whose value gets negative. Then artificial variables are removed.
+    /// This needs such a variable in every such row, and all the simplex criterions to be nonnegative.
+    pub(super) fn start_dual(&mut self, options: &SolverOptions) -> bool {
+        if self.criterions().iter().any(|c| *c < -options.optimality_tolerance) {return false}
+
+        let rows = (0..(self.bases.len() - 1))
+            .filter(|&i| self.bases[i].variable.is_artificial())
+            .map(|i| (0..self.variables.len()).find(|&j|
+                !self.variables[j].is_structural() && !self.variables[j].is_artificial() &&
+                self.coefficients.column_iter(j).unwrap().enumerate()
+                    .all(|(k, c)| *c == if k == i {-1.} else {0.})
+            ).map(|j| (i, j)))
+            .collect::<Option<Vec<_>>>();
+        let Some(rows) = rows else {return false};
+
+        for (i, j) in rows {
+            self.bases[i].value = -self.bases[i].value;
+            for c in &mut self.coefficients[i] {*c = -*c}
+            self.bases[i].variable = self.variables[j].clone();
+            self.origins[i].unit_column = j;
+            self.origins[i].flipped     = !self.origins[i].flipped;
+        }
+
+        /* Artificial columns are after all the others, so `unit_column`s are kept */
+        let remaining = self.variables.iter().take_while(|variable| !variable.is_artificial()).count();
+        self.variables.truncate(remaining);
+        self.objective.truncate(remaining);
+        for row in &mut self.coefficients.rows {row.truncate(remaining)}
+        self.coefficients.row_size = remaining;
+
+        true
+    }
+
+    /// Search the pivot of dual simplex method:
+    /// 
+    /// - Row index where the base variable's value is the most negative
+    /// - Column index where `simplex criterion / -coefficient` is minimum among negative coefficients in the row
+    /// 
+    /// When the row has no negative coefficient, the problem is infeasible.
+    fn dual_pivot(&self, options: &SolverOptions) -> Result<Pivot, SolveError> {
+        let (row, _) = (0..(self.bases.len() - 1))
+            .map(|i| (i, self.bases[i].value))
+            .reduce(|(min_value_row, min), (i, value)| {
+                if value < min {(i, value)} else {(min_value_row, min)}
+            }).unwrap();
+
+        let (column, _) = (0..self.variables.len())
+            .filter(|&j| !self.variables[j].is_artificial() && self.coefficients[row][j] < -options.pivot_tolerance)
+            .map(|j| (j, self.criterions()[j].max(0.) / -self.coefficients[row][j]))
+            .reduce(|(min_ratio_column, min), (j, ratio)| {
+                if ratio < min {(j, ratio)} else {(min_ratio_column, min)}
+            }).ok_or(SolveError::Infeasible)?;
+
+        Ok(Pivot {
+            row,
+            column,
+            value: self.coefficients[row][column],
+        })
+    }
+
+    /// Repeat pivot operations of dual simplex method until all base variables get nonnegative.
+    /// The table has to be dual feasible: all simplex criterions are nonnegative.
+    pub(super) fn iterate_dual(&mut self, options: &SolverOptions, progress: &mut Progress) -> Result<(), SolveError> {
+        while self.bases[..(self.bases.len() - 1)].iter().any(|base| base.value < -options.feasibility_tolerance) {
+            progress.check(options)?;
+
+            let p = self.dual_pivot(options)?;
+            self.bases[p.row].variable = self.variables[p.column].clone();
+            self.update_coefficients(p);
+
+            if self.bases.iter().any(|base| !base.value.is_finite()) {
+                return Err(SolveError::NumericalTrouble)
+            }
+
+            progress.iterations += 1;
+
+            #[cfg(test)] println!("{self:?}");
+        }
+
+        Ok(())
+    }
+}
@@ -1,141 +1,542 @@
 #[cfg(test)] mod test;
-
-use std::collections::HashMap;
-use crate::problem::Problem;
+mod cut;
+mod dual;
+mod reoptimize;
+mod revised;
+mod render;
+mod sensitivity;
+mod standard;
+mod steps;
+pub use sensitivity::SensitivityReport;
+pub use steps::{SimplexSteps, Step, Phase};
+use standard::StandardForm;
+use revised::RevisedTableau;
+
+use std::{borrow::Cow, collections::HashMap, time::Instant};
+use crate::problem::{Problem, Sense, Substitution};
+use crate::error::{SolveError, PivotError};
+use crate::options::{SolverOptions, PivotRule, Algorithm};
 use crate::components::{variable::Variable, scalor::Scalor, matrix::Matrix};
 
 
-#[derive(PartialEq)]
+/// Simplex tableau, whose last row is the objective function in the sense of maximization.
+/// Built by `Table::new`, and solved by `Table::steps` or by hand with `Table::pivot_on`.
+#[derive(Clone, PartialEq)]
 pub struct Table {
+    sense:        Sense,
     variables:    Vec<Variable>,      // This's index is just the column number of `coefficients`
     bases:        Vec<BaseVariable>,  // This's index is just the row number of `coefficients`
     coefficients: Matrix<Scalor>,
+    origins:      Vec<RowOrigin>,     // This's index is just the row number of the problem's constraints
+    objective:    Vec<Scalor>,        // Coefficients of the objective function in the sense of maximization, for each column
+    substitution: Substitution,       // Bounds of the problem replaced in the table
+}
+
+/// How a row of the initial table was made from the constraint
+#[derive(Debug, Clone, PartialEq)]
+struct RowOrigin {
+    /// The column that was the unit vector of the row in the initial table.
+    /// Through pivot operations, this column holds the corresponding column of the inverse of basis.
+    unit_column: usize,
+    /// Whether the row was multiplied by -1 for its right-hand side to be nonnegative
+    flipped:     bool,
+    /// The right-hand side of the constraint, before flipped
+    rhs:         Scalor,
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, PartialEq)]
 struct BaseVariable {
     variable: Variable,
     value:    Scalor,
 }
 
-#[derive(Debug, PartialEq)]
-struct Pivot {
-    row:    usize,
-    column: usize,
-    value:  Scalor,
+/// Position and value of a pivot element in the table
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pivot {
+    pub row:    usize,
+    pub column: usize,
+    pub value:  Scalor,
+}
+
+/// Pivot operations done and time elapsed through the phases
+struct Progress {
+    iterations: usize,
+    limit:      usize,
+    started:    Instant,
+} impl Progress {
+    fn new(table: &Table, options: &SolverOptions) -> Self {
+        Self::with_size(table.coefficients.row_size, table.coefficients.column_size, options)
+    }
+
+    /// For the table of `standard`, including the objective row
+    fn of_standard_form(standard: &StandardForm, options: &SolverOptions) -> Self {
+        Self::with_size(standard.columns.row_size, standard.rhs.len() + 1, options)
+    }
+
+    /// For a table of `row_size` columns and `column_size` rows
+    fn with_size(row_size: usize, column_size: usize, options: &SolverOptions) -> Self {
+        Self {
+            iterations: 0,
+            limit:      options.iteration_limit(row_size, column_size),
+            started:    Instant::now(),
+        }
+    }
+
+    /// Check the limits before a pivot operation
+    fn check(&self, options: &SolverOptions) -> Result<(), SolveError> {
+        if self.iterations == self.limit {
+            return Err(SolveError::IterationLimit { iterations: self.iterations })
+        }
+        if let Some(time_limit) = options.time_limit {
+            let elapsed = self.started.elapsed();
+            if elapsed >= time_limit {
+                return Err(SolveError::TimeLimit { elapsed })
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Pivot rule and degenerate pivots in a row through a phase
+struct Iteration {
+    /// The columns of base variables at the beginning of the phase
+    lexicographic_columns: Vec<usize>,
+    rule:                  PivotRule,
+    degenerate_count:      usize,
+} impl Iteration {
+    fn new(table: &Table, options: &SolverOptions) -> Self {
+        Self {
+            lexicographic_columns: table.base_columns(),
+            rule:                  options.pivot_rule,
+            degenerate_count:      0,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Solution {
+    /// Values of all the normal variables, including ones that are 0
+    #[cfg_attr(feature = "serde", serde(with = "crate::components::variable::map"))]
     pub variables:     HashMap<Variable, Scalor>,
     pub optimal_value: Scalor,
+    /// Shadow price of each constraint: how much the optimal value increases
+    /// per unit increase of the right-hand side. This's index is just the row number of the constraints.
+    pub duals:         Vec<Scalor>,
+    /// Reduced cost of each normal variable: how much the objective function increases
+    /// per unit increase of the variable from the optimal solution
+    #[cfg_attr(feature = "serde", serde(with = "crate::components::variable::map"))]
+    pub reduced_costs: HashMap<Variable, Scalor>,
+    /// Distance of each constraint's left-hand side to its right-hand side:
+    /// the slack variable of a `≤` row, the surplus variable of a `≥` row and 0 for a `=` row
+    pub slacks:        Vec<Scalor>,
+    /// Value of each constraint's left-hand side
+    pub activities:    Vec<Scalor>,
+    /// Whether each constraint holds with equality
+    pub binding:       Vec<bool>,
+    /// Relative gap between the best bound of branch-and-bound and `optimal_value`,
+    /// or `None` when the problem has no integer variables
+    pub mip_gap:       Option<Scalor>,
+    pub sensitivity:   SensitivityReport,
 }
 
 impl Table {
-    pub fn from_problem(problem: Problem) -> Self {
-        let Problem { objective_function, condition } = problem.into_standard_form();
+    /// Build the initial table of `problem` from its `StandardForm`: after `Problem::substitute_bounds` replaces
+    /// the variables with other bounds than `0 ≤ x`, rows whose right-hand side is negative are multiplied by -1.
+    /// Then each row takes the slack variable whose column is the unit vector of the row
+    /// as its base variable, or a new artificial variable when there's no such slack variable.
+    /// The value of the objective row starts at the constant of the objective function.
+    pub(crate) fn from_problem(problem: Problem) -> Self {
+        Self::from_standard_form(StandardForm::new(problem))
+    }
 
-        let variables = condition.x;
+    /// The constraint matrix, sparse until here, gets dense only here and in `Table::from_basis`
+    /// of revised simplex method.
+    fn from_standard_form(standard: StandardForm) -> Self {
+        let constant = standard.constant();
+        let StandardForm { sense, variables, columns, rhs, origins, objective, substitution } = standard;
 
-        let bases = {
-            let slack_variables = variables.clone().into_iter()
-                .skip_while(Variable::is_normal);
-            let mut bases = slack_variables.zip(condition.b)
-                .map(|(variable, value)| BaseVariable { variable, value })
-                .collect::<Vec<_>>();
-            bases.push(BaseVariable {
-                variable: Variable::Object,
-                value:    0.,
-            });
-            bases
-        };
+        let mut coefficients = columns.to_dense();
+        coefficients.push_row(objective.iter().map(|c| -c).collect()).unwrap();
 
-        let coefficients = {
-            let mut coefficients = condition.A;
+        let bases = origins.iter().zip(rhs)
+            .map(|(origin, value)| BaseVariable { variable: variables[origin.unit_column].clone(), value })
+            .chain([BaseVariable { variable: Variable::Object, value: constant }])
+            .collect();
 
-            let objective_coefficients = variables.clone().into_iter()
-                .map(|var| objective_function.terms.iter()
-                    .find(|term| term.variable == var)
-                    .map_or(0., |term| -term.coefficient)
-                ).collect();
+        Self { sense, variables, bases, coefficients, origins, objective, substitution }
+    }
 
-            coefficients.push_row(objective_coefficients).unwrap();
-            coefficients
-        };
+    #[cfg(test)] pub fn solve(self) -> Result<Solution, SolveError> {
+        self.solve_with(&SolverOptions::default())
+    }
 
-        Self { variables, bases, coefficients }
+    #[cfg(test)] pub fn solve_with(mut self, options: &SolverOptions) -> Result<Solution, SolveError> {
+        let mut progress = Progress::new(&self, options);
+        self.solve_with_cuts(options, 0, &mut progress)
     }
 
-    pub fn solve(mut self) -> Result<Solution, String> {
-        const UPDATE_LIMIT: usize = 5;
-        let mut update_count = 0;
+    /// Solve `problem` as if all the variables were continuous. With `Algorithm::Revised`, the solution is read
+    /// from the optimal basis of the sparse `StandardForm` without building the table. See `solve_problem`.
+    pub(crate) fn solve_relaxation(problem: Problem, options: &SolverOptions) -> Result<Solution, SolveError> {
+        if options.algorithm != Algorithm::Revised {
+            return Self::solve_problem(problem, options, 0).map(|(_, solution)| solution)
+        }
+        let standard = StandardForm::new(problem);
+        let basis = standard.solve_revised(options, &mut Progress::of_standard_form(&standard, options))?;
+        Ok(RevisedTableau::new(&standard, &basis).solution(options))
+    }
 
-        #[cfg(test)] println!("{self:?}");
+    /// Solve `problem` as if all the variables were continuous by `solve_with_cuts`, and return the optimal table
+    /// with the solution. With `Algorithm::Revised`, revised simplex method solves the sparse `StandardForm` first,
+    /// and the table is built at its optimal basis.
+    pub(crate) fn solve_problem(problem: Problem, options: &SolverOptions, cut_rounds: usize) -> Result<(Self, Solution), SolveError> {
+        let standard = StandardForm::new(problem);
+        let mut progress = Progress::of_standard_form(&standard, options);
+        let mut table = match options.algorithm {
+            Algorithm::Revised => {
+                let basis = standard.solve_revised(options, &mut progress)?;
+                Self::from_basis(standard, &basis)
+            }
+            Algorithm::Primal | Algorithm::Dual => Self::from_standard_form(standard),
+        };
+        let solution = table.solve_with_cuts(options, cut_rounds, &mut progress)?;
+        Ok((table, solution))
+    }
 
-        while !self.is_optimal() {
-            let p = self.pivot();
-            self.bases[p.row].variable = self.variables[p.column].clone();
-            self.update_coefficients(p);
+    /// Solve by two-phase simplex method: when some base variables are artificial ones,
+    /// Phase I searches a feasible basis before the Phase II optimizes the objective function.
+    /// With `Algorithm::Dual`, dual simplex method replaces Phase I if possible.
+    /// `Algorithm::Revised` is as `Algorithm::Primal` here: see `solve_problem`.
+    /// 
+    /// Then repeat adding a Gomory fractional cut and reoptimizing at most `cut_rounds` times
+    /// while some structural base variable is fractional.
+    /// Only for a pure integer problem: see `add_gomory_cut`.
+    /// 
+    /// After cuts are added, duals and sensitivity are the ones of the relaxation strengthened by them.
+    /// The table is left optimal, to be reoptimized by `reoptimize`.
+    fn solve_with_cuts(&mut self, options: &SolverOptions, cut_rounds: usize, progress: &mut Progress) -> Result<Solution, SolveError> {
+        #[cfg(test)] println!("{self:?}");
 
-            update_count += 1;
-            if update_count == UPDATE_LIMIT {
-                return Err((|| format!("Reaeched UPDATE_LIMIT"))())
+        if self.bases.iter().any(|base| base.variable.is_artificial()) {
+            if options.algorithm == Algorithm::Dual && self.start_dual(options) {
+                self.iterate_dual(options, progress)?;
+            } else {
+                self.phase_one(options, progress)?;
             }
+        }
+        self.iterate(options, progress)?;
 
-            #[cfg(test)] println!("{self:?}");
+        /* Each cut keeps the table dual feasible, so dual simplex reoptimizes it from the current basis */
+        for _ in 0..cut_rounds {
+            if !self.add_gomory_cut(options) {break}
+            self.iterate_dual(options, progress)?;
+            self.iterate(options, progress)?;
         }
 
-        Ok(Solution {
-            optimal_value: self.object_value(),
-            variables:     HashMap::from_iter(
-                self.bases.into_iter()
-                    .filter_map(|BaseVariable { variable, value }| variable.is_normal().then(|| (variable, value)))
-            ),
+        Ok(self.solution(options))
+    }
+}
+
+/// An optimal basis that `Solution` is read from: a `Table`, or `RevisedTableau` of revised simplex method,
+/// which computes the rows and the columns of its table on demand
+trait Tableau {
+    fn sense(&self) -> Sense;
+    fn variables(&self) -> &[Variable];
+    fn origins(&self) -> &[RowOrigin];
+    /// Coefficients of the objective function in the sense of maximization, for each column
+    fn objective(&self) -> &[Scalor];
+    fn substitution(&self) -> &Substitution;
+    /// Column and value of the base variable of each constraint row
+    fn base_values(&self) -> Vec<(usize, Scalor)>;
+    /// The objective row
+    fn criterions(&self) -> &[Scalor];
+    /// The value in the objective row
+    fn object_value(&self) -> Scalor;
+    /// Constraint row `row` of the table
+    fn row(&self, row: usize) -> Cow<'_, [Scalor]>;
+    /// Column `column` of the constraint rows of the table
+    fn column(&self, column: usize) -> Cow<'_, [Scalor]>;
+
+    fn value_of(&self, variable: &Variable) -> Scalor {
+        self.base_values().into_iter()
+            .find(|(j, _)| &self.variables()[*j] == variable)
+            .map_or(0., |(_, value)| value)
+    }
+
+    /// Values of the structural variables at the basic solution
+    fn structural_values(&self) -> HashMap<Variable, Scalor> {
+        let mut values = HashMap::from_iter(self.variables().iter()
+            .filter(|variable| variable.is_structural())
+            .map(|variable| (variable.clone(), 0.))
+        );
+        for (j, value) in self.base_values() {
+            if let Some(base) = values.get_mut(&self.variables()[j]) {*base = value}
+        }
+        values
+    }
+
+    /// Read the solution of the original problem from an optimal basis
+    fn solution(&self, options: &SolverOptions) -> Solution {
+        /* Values in the objective row are in the sense of maximization */
+        let sense_sign = match self.sense() {
+            Sense::Maximize =>  1.,
+            Sense::Minimize => -1.,
+        };
+
+        let duals = self.origins().iter()
+            .map(|RowOrigin { unit_column, flipped, .. }| {
+                let dual = sense_sign * self.criterions()[*unit_column];
+                if *flipped {-dual} else {dual}
+            }).collect();
+
+        let reduced_costs = HashMap::from_iter(
+            self.variables().iter().zip(self.criterions())
+                .filter(|(variable, _)| variable.is_structural())
+                .map(|(variable, c)| (variable.clone(), -sense_sign * c))
+        );
+
+        let (slacks, activities) = self.origins().iter().enumerate()
+            .map(|(i, RowOrigin { rhs, .. })| {
+                let (slack, surplus) = (Variable::Slack { id: i + 1 }, Variable::Surplus { id: i + 1 });
+                if self.variables().contains(&slack) {
+                    let value = self.value_of(&slack);
+                    (value, rhs - value)
+                } else if self.variables().contains(&surplus) {
+                    let value = self.value_of(&surplus);
+                    (value, rhs + value)
+                } else {
+                    (0., *rhs)
+                }
+            }).unzip::<_, _, Vec<_>, Vec<_>>();
+
+        let binding = slacks.iter()
+            .map(|slack| *slack <= options.feasibility_tolerance)
+            .collect();
+
+        let sensitivity = sensitivity::ranging(self, options);
+
+        let optimal_value = match self.sense() {
+            Sense::Maximize =>  self.object_value(),
+            Sense::Minimize => -self.object_value(),
+        };
+
+        self.substitution().restore(Solution {
+            optimal_value,
+            variables: self.structural_values(),
+            duals,
+            reduced_costs,
+            slacks,
+            activities,
+            binding,
+            mip_gap: None,
+            sensitivity,
         })
     }
 }
 
+const _: () = {
+    impl Tableau for Table {
+        fn sense(&self) -> Sense {
+            self.sense
+        }
+        fn variables(&self) -> &[Variable] {
+            &self.variables
+        }
+        fn origins(&self) -> &[RowOrigin] {
+            &self.origins
+        }
+        fn objective(&self) -> &[Scalor] {
+            &self.objective
+        }
+        fn substitution(&self) -> &Substitution {
+            &self.substitution
+        }
+        fn base_values(&self) -> Vec<(usize, Scalor)> {
+            self.base_columns().into_iter().zip(self.basic_values()).collect()
+        }
+        fn criterions(&self) -> &[Scalor] {
+            Table::criterions(self)
+        }
+        fn object_value(&self) -> Scalor {
+            Table::object_value(self)
+        }
+        fn row(&self, row: usize) -> Cow<'_, [Scalor]> {
+            Cow::Borrowed(&self.coefficients[row])
+        }
+        fn column(&self, column: usize) -> Cow<'_, [Scalor]> {
+            Cow::Owned(self.coefficients.rows[..(self.bases.len() - 1)].iter().map(|row| row[column]).collect())
+        }
+    }
+};
+
+/// Read-only view of the table and manual pivot operations, for algorithms on top of it
+impl Table {
+    /// Build the initial table of `problem` whose bounds are substituted ( see [`Problem::bound`] ),
+    /// as if all the variables were continuous. Its objective value includes the constant of the objective function,
+    /// but the basic values are of the substituted variables.
+    pub fn new(problem: Problem) -> Result<Self, SolveError> {
+        problem.validate()?;
+        Ok(Self::from_problem(problem))
+    }
+
+    pub fn sense(&self) -> Sense {
+        self.sense
+    }
+
+    /// Variables of the columns
+    pub fn variables(&self) -> &[Variable] {
+        &self.variables
+    }
+
+    /// Base variable of each constraint row
+    pub fn basis(&self) -> impl Iterator<Item = &Variable> {
+        self.bases[..(self.bases.len() - 1)].iter().map(|base| &base.variable)
+    }
+
+    /// Value of each base variable, in the order of `basis`
+    pub fn basic_values(&self) -> impl Iterator<Item = Scalor> + '_ {
+        self.bases[..(self.bases.len() - 1)].iter().map(|base| base.value)
+    }
+
+    /// Simplex criterion of each column, the objective row in the sense of maximization.
+    /// The table is optimal when all of them are nonnegative.
+    /// 
+    /// Each of them is the negative of the reduced cost in the sense of maximization,
+    /// which is the opposite sign to `Solution::reduced_costs` of a maximizing problem.
+    pub fn criterions(&self) -> &[Scalor] {
+        self.coefficients.rows.last().unwrap()
+    }
+
+    /// Coefficients of the constraint rows, followed by the objective row
+    pub fn coefficients(&self) -> &Matrix<Scalor> {
+        &self.coefficients
+    }
+
+    /// The value of objective function in the sense of the problem at the current basic solution
+    pub fn objective_value(&self) -> Scalor {
+        match self.sense {
+            Sense::Maximize =>  self.object_value(),
+            Sense::Minimize => -self.object_value(),
+        }
+    }
+
+    /// Whether all simplex criterions are `>= -options.optimality_tolerance`, except for artificial variables
+    pub fn is_optimal(&self, options: &SolverOptions) -> bool {
+        self.criterions().iter().zip(&self.variables)
+            .all(|(&c, variable)| variable.is_artificial() || c >= -options.optimality_tolerance)
+    }
+
+    /// Search the pivot of the next pivot operation by `options.pivot_rule`, or `None` when the table is optimal
+    pub fn pivot(&self, options: &SolverOptions) -> Result<Option<Pivot>, PivotError> {
+        let Some(column) = self.entering_column(options.pivot_rule, options) else {return Ok(None)};
+        let row = self.leaving_row(column, options.pivot_rule, &self.base_columns(), options)
+            .ok_or(PivotError::Unbounded { column })?;
+        Ok(Some(Pivot { row, column, value: self.coefficients[row][column] }))
+    }
+
+    /// Pivot operation at `row` and `column`: the variable of `column` enters the basis
+    /// and the base variable of `row` leaves it. The objective row can't be `row`.
+    /// 
+    /// Any element whose absolute value is greater than `options.pivot_tolerance` can be the pivot,
+    /// so the basic solution may get infeasible or worse.
+    pub fn pivot_on(&mut self, row: usize, column: usize, options: &SolverOptions) -> Result<Pivot, PivotError> {
+        let rows = self.bases.len() - 1;
+        if row >= rows {
+            return Err(PivotError::RowOutOfBound { size: rows, index: row })
+        }
+        if column >= self.variables.len() {
+            return Err(PivotError::ColumnOutOfBound { size: self.variables.len(), index: column })
+        }
+        let value = self.coefficients[row][column];
+        if value.abs() <= options.pivot_tolerance {
+            return Err(PivotError::ZeroElement { row, column })
+        }
+
+        let pivot = Pivot { row, column, value };
+        self.bases[row].variable = self.variables[column].clone();
+        self.update_coefficients(pivot);
+        Ok(pivot)
+    }
+}
+
 impl Table {
     fn object_value(&self) -> Scalor {
         self.bases.last().unwrap().value
     }
 
-    fn criterions(&self) -> &Vec<Scalor> {
-        self.coefficients.rows.last().unwrap()
+    fn column_of(&self, variable: &Variable) -> usize {
+        self.variables.iter().position(|v| v == variable).unwrap()
     }
 
-    fn is_optimal(&self) -> bool {
-        self.criterions().iter().all(|&c| c >= 0.)
+    /// Column indices of the base variables, in the order of rows
+    fn base_columns(&self) -> Vec<usize> {
+        self.bases[..(self.bases.len() - 1)].iter()
+            .map(|base| self.column_of(&base.variable))
+            .collect()
     }
 
     /// Search
     /// 
     /// - Row index where the max-increase is minimum
-    /// - Column index where the simplex criterion is minimum
+    /// - Column index where the simplex criterion is negative
     /// 
     /// and returns `(row index, column index)`\
     /// e.t. `(pivot row index, pivot column index)`
-    fn pivot(&self) -> Pivot {
-        let (min_criterion_coloumn, _) = self.criterions().iter()
-            .enumerate()
-            .reduce(|(min_criterion_coloumn, min), (i, c)| {
-                if c < &min {(i, c)} else {(min_criterion_coloumn, min)}
-            }).unwrap();
-        let max_increases = self.coefficients.column_iter(min_criterion_coloumn).unwrap()
-            .enumerate()
-            .take_while(|(i, _)| *i < self.coefficients.column_size - 1)
-            .map(|(i, c)| self.bases[i].value / c);
-        let (min_maxinc_row, _) = max_increases
+    /// 
+    /// How to choose among candidates depends on `rule`.
+    /// `lexicographic_columns` are the columns of base variables at the beginning of current phase,
+    /// which `PivotRule::Lexicographic` compares rows on.
+    /// 
+    /// Artificial variables never enter the basis.
+    /// When no row bounds the increase, the problem is unbounded.
+    fn pivot_by(&self, rule: PivotRule, lexicographic_columns: &[usize], options: &SolverOptions) -> Result<Pivot, SolveError> {
+        let column = self.entering_column(rule, options).unwrap(/* not optimal */);
+        let row = self.leaving_row(column, rule, lexicographic_columns, options).ok_or(SolveError::Unbounded)?;
+        Ok(Pivot {
+            row,
+            column,
+            value: self.coefficients[row][column],
+        })
+    }
+
+    /// Column of the entering variable by `rule`, or `None` when the table is optimal
+    fn entering_column(&self, rule: PivotRule, options: &SolverOptions) -> Option<usize> {
+        let mut candidate_columns = self.criterions().iter()
             .enumerate()
-            .filter(|(_, maxinc)| *maxinc >= 0.)
-            .reduce(|(min_maxinc_row, min_maxinc), (i, maxinc)|
-                if maxinc < min_maxinc {(i, maxinc)} else {(min_maxinc_row, min_maxinc)}
-            ).unwrap();
-        
-        Pivot {
-            row:    min_maxinc_row,
-            column: min_criterion_coloumn,
-            value:  self.coefficients[min_maxinc_row][min_criterion_coloumn],
+            .filter(|(j, c)| !self.variables[*j].is_artificial() && **c < -options.optimality_tolerance);
+        match rule {
+            PivotRule::Bland => candidate_columns.next(),
+            PivotRule::Dantzig | PivotRule::Lexicographic => candidate_columns
+                .reduce(|(min_criterion_coloumn, min), (j, c)| {
+                    if c < min {(j, c)} else {(min_criterion_coloumn, min)}
+                }),
+        }.map(|(column, _)| column)
+    }
+
+    /// Row of the leaving variable by `rule` for the entering `column`, or `None` when no element of `column` is positive
+    fn leaving_row(&self, column: usize, rule: PivotRule, lexicographic_columns: &[usize], options: &SolverOptions) -> Option<usize> {
+        let max_increases = (0..(self.bases.len() - 1))
+            .filter(|&i| self.coefficients[i][column] > options.pivot_tolerance)
+            .map(|i| (i, self.bases[i].value / self.coefficients[i][column]))
+            .collect::<Vec<_>>();
+        let min_maxinc = max_increases.iter()
+            .map(|(_, maxinc)| *maxinc)
+            .reduce(Scalor::min)?;
+        let mut candidate_rows = max_increases.into_iter()
+            .filter(|(_, maxinc)| *maxinc <= min_maxinc + options.feasibility_tolerance)
+            .map(|(i, _)| i);
+        match rule {
+            PivotRule::Dantzig => candidate_rows.next(),
+            PivotRule::Bland   => candidate_rows.min_by_key(|&i| self.column_of(&self.bases[i].variable)),
+            PivotRule::Lexicographic => candidate_rows.reduce(|min_row, i| {
+                let ratio = |i: usize, j: usize| self.coefficients[i][j] / self.coefficients[i][column];
+                let less = lexicographic_columns.iter()
+                    .map(|&j| (ratio(i, j), ratio(min_row, j)))
+                    .find(|(r, min_r)| (r - min_r).abs() > options.pivot_tolerance)
+                    .is_some_and(|(r, min_r)| r < min_r);
+                if less {i} else {min_row}
+            }),
         }
     }
 
@@ -162,6 +563,106 @@ impl Table {
     }
 }
 
+impl Table {
+    /// Repeat pivot operations until the table gets optimal
+    fn iterate(&mut self, options: &SolverOptions, progress: &mut Progress) -> Result<(), SolveError> {
+        let mut iteration = Iteration::new(self, options);
+        while self.step(&mut iteration, options, progress)?.is_some() {}
+        Ok(())
+    }
+
+    /// A pivot operation of `iterate`, returning the pivot and the leaving variable,
+    /// or `None` when the table is optimal.
+    /// 
+    /// Too many degenerate pivots in a row suggest cycling. Then switch to Bland's rule,
+    /// which never cycles.
+    fn step(&mut self, iteration: &mut Iteration, options: &SolverOptions, progress: &mut Progress) -> Result<Option<(Pivot, Variable)>, SolveError> {
+        if self.is_optimal(options) {return Ok(None)}
+        progress.check(options)?;
+
+        let p = self.pivot_by(iteration.rule, &iteration.lexicographic_columns, options)?;
+        if self.bases[p.row].value <= options.feasibility_tolerance {
+            iteration.degenerate_count += 1;
+            if iteration.degenerate_count >= options.stall_limit {iteration.rule = PivotRule::Bland}
+        } else {
+            iteration.degenerate_count = 0;
+        }
+
+        let leaving = std::mem::replace(&mut self.bases[p.row].variable, self.variables[p.column].clone());
+        self.update_coefficients(p);
+
+        if self.bases.iter().any(|base| !base.value.is_finite()) {
+            return Err(SolveError::NumericalTrouble)
+        }
+
+        progress.iterations += 1;
+
+        #[cfg(test)] println!("{self:?}");
+
+        Ok(Some((p, leaving)))
+    }
+
+    /// Phase I: maximize `- Σ artificial variables` to find a feasible basis,
+    /// then drive remaining artificial variables out of the basis and restore the objective row.
+    fn phase_one(&mut self, options: &SolverOptions, progress: &mut Progress) -> Result<(), SolveError> {
+        let objective = self.start_phase_one();
+        self.iterate(options, progress)?;
+        self.finish_phase_one(objective, options)
+    }
+
+    /// Replace the objective row with the one of Phase I, and return the original criterions and value
+    fn start_phase_one(&mut self) -> (Vec<Scalor>, Scalor) {
+        let (objective, value) = (self.criterions().to_vec(), self.object_value());
+
+        self.set_objective(self.variables.iter()
+            .map(|variable| if variable.is_artificial() {1.} else {0.})
+            .collect(), 0.
+        );
+        (objective, value)
+    }
+
+    /// After Phase I gets optimal, check feasibility, drive remaining artificial variables out of the basis
+    /// and restore the objective row `start_phase_one` returned
+    fn finish_phase_one(&mut self, (objective, value): (Vec<Scalor>, Scalor), options: &SolverOptions) -> Result<(), SolveError> {
+        if self.object_value() < -options.feasibility_tolerance {
+            return Err(SolveError::Infeasible)
+        }
+
+        for row in 0..(self.bases.len() - 1) {
+            if !self.bases[row].variable.is_artificial() {continue}
+
+            /* An artificial variable that can't be driven out stays in the basis at value 0:
+               its row is a linear combination of the other rows */
+            if let Some(column) = (0..self.variables.len()).find(|&j|
+                !self.variables[j].is_artificial() && self.coefficients[row][j].abs() > options.pivot_tolerance
+            ) {
+                self.bases[row].variable = self.variables[column].clone();
+                self.update_coefficients(Pivot { row, column, value: self.coefficients[row][column] });
+            }
+        }
+
+        self.set_objective(objective, value);
+        Ok(())
+    }
+
+    /// Replace the objective row with `criterions` and `value`,
+    /// and eliminate its components at the base variables' columns
+    fn set_objective(&mut self, mut criterions: Vec<Scalor>, mut value: Scalor) {
+        for (base, row) in self.bases.iter().zip(&self.coefficients.rows).take(self.bases.len() - 1) {
+            let column = self.column_of(&base.variable);
+            let rate = criterions[column];
+            if rate == 0. {continue}
+
+            value -= base.value * rate;
+            for (c, r) in criterions.iter_mut().zip(row) {
+                *c -= r * rate;
+            }
+        }
+
+        *self.coefficients.rows.last_mut().unwrap() = criterions;
+        self.bases.last_mut().unwrap().value = value;
+    }
+}
 
 const _: () = {
     impl std::fmt::Debug for Table {
@@ -182,15 +683,11 @@ const _: () = {
                         .collect::<Vec<_>>()
                     ).collect::<Vec<_>>();
 
-                let mut maxwidth_by_column = Vec::with_capacity(self.coefficients.row_size);
-                for x in 0..self.coefficients.row_size {
-                    maxwidth_by_column.push(
-                        (0..self.coefficients.column_size)
-                            .map(|y| widths[y][x])
-                            .reduce(usize::max).unwrap_or(0)
-                    )
-                }
-                maxwidth_by_column
+                (0..self.coefficients.row_size)
+                    .map(|x| widths.iter()
+                        .map(|row| row[x])
+                        .reduce(usize::max).unwrap_or(0)
+                    ).collect::<Vec<_>>()
             };
 
             let base_info_width = base_var_maxwidth + 3/* ` = ` */ + base_value_maxwidth;
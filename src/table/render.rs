@@ -0,0 +1,155 @@
+use crate::error::SolveError;
+use crate::components::{variable::Variable, scalor::Scalor};
+use super::{Table, Pivot, BaseVariable, SimplexSteps};
+
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format { Markdown, Latex }
+
+impl Table {
+    /// Render as a Markdown table of the same layout as `Debug`, with values rounded to `precision` decimal places
+    /// and trailing zeros removed. With `pivot`, the pivot element is in bold,
+    /// and the entering column and the leaving row are marked by `↓` and `←`.
+    pub fn to_markdown(&self, pivot: Option<&Pivot>, precision: usize) -> String {
+        self.render(Format::Markdown, pivot, precision)
+    }
+
+    /// Render as a LaTeX `array` environment, to be put in math mode, of the same layout as `Debug`,
+    /// with values rounded as `to_markdown`. With `pivot`, the pivot element is `\boxed`, and the entering column and the leaving row are marked by `\downarrow` and `\leftarrow`.
+    ///
+    /// Variables are written as `x_{1}` for `x1`, `x_{1}^{-}` for `neg#x1`, and `s_{i}`, `e_{i}`, `a_{i}`, `z`
+    /// for the slack, surplus, artificial variables of the `i`-th row and the objective function.
+    pub fn to_latex(&self, pivot: Option<&Pivot>, precision: usize) -> String {
+        self.render(Format::Latex, pivot, precision)
+    }
+
+    fn render(&self, format: Format, pivot: Option<&Pivot>, precision: usize) -> String {
+        let variable = |variable: &Variable| match format {
+            Format::Markdown => format!("{variable:?}").replace('|', "\\|"),
+            Format::Latex    => latex_variable(variable),
+        };
+        let element = |value: String| match format {
+            Format::Markdown => format!("**{value}**"),
+            Format::Latex    => format!("\\boxed{{{value}}}"),
+        };
+        let (entering, leaving) = match format {
+            Format::Markdown => (" ↓", " ←"),
+            Format::Latex    => (" \\downarrow", " \\leftarrow"),
+        };
+
+        let header = [String::new(), String::new()].into_iter()
+            .chain(self.variables.iter().enumerate().map(|(j, v)| match pivot {
+                Some(pivot) if pivot.column == j => variable(v) + entering,
+                _ => variable(v),
+            }))
+            .collect::<Vec<_>>();
+        let rows = self.bases.iter().zip(&self.coefficients.rows).enumerate()
+            .map(|(i, (BaseVariable { variable: base, value }, row))| {
+                let label = match pivot {
+                    Some(pivot) if pivot.row == i => variable(base) + leaving,
+                    _ => variable(base),
+                };
+                [label, round(*value, precision)].into_iter()
+                    .chain(row.iter().enumerate().map(|(j, c)| match pivot {
+                        Some(pivot) if (pivot.row, pivot.column) == (i, j) => element(round(*c, precision)),
+                        _ => round(*c, precision),
+                    }))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut rendered = String::new();
+        match format {
+            Format::Markdown => {
+                let line = |cells: &[String]| format!("| {} |\n", cells.join(" | "));
+                rendered += &line(&header);
+                rendered += &format!("|---|---:|{}\n", "---:|".repeat(self.variables.len()));
+                for row in &rows {
+                    rendered += &line(row);
+                }
+            }
+            Format::Latex => {
+                let line = |cells: &[String]| format!("  {} \\\\\n", cells.join(" & "));
+                rendered += &format!("\\begin{{array}}{{c|r|{}}}\n", "r".repeat(self.variables.len()));
+                rendered += &line(&header);
+                rendered += "  \\hline\n";
+                for (i, row) in rows.iter().enumerate() {
+                    if i == rows.len() - 1 {rendered += "  \\hline\n"}
+                    rendered += &line(row);
+                }
+                rendered += "\\end{array}\n";
+            }
+        }
+        rendered
+    }
+}
+
+impl SimplexSteps {
+    /// Solve step by step and render all the tables by `Table::to_markdown`,
+    /// each with the pivot of the step from it, separated by blank lines
+    pub fn to_markdown(self, precision: usize) -> Result<String, SolveError> {
+        self.render(Format::Markdown, precision)
+    }
+
+    /// Solve step by step and render all the tables by `Table::to_latex`,
+    /// each with the pivot of the step from it and in `\[ \]`, separated by blank lines
+    pub fn to_latex(self, precision: usize) -> Result<String, SolveError> {
+        self.render(Format::Latex, precision)
+    }
+
+    fn render(mut self, format: Format, precision: usize) -> Result<String, SolveError> {
+        let mut tables = Vec::new();
+        loop {
+            let table = self.table().clone();
+            let step = self.next().transpose()?;
+            tables.push(table.render(format, step.as_ref().map(|step| &step.pivot), precision));
+            if step.is_none() {break}
+        }
+
+        Ok(match format {
+            Format::Markdown => tables.join("\n"),
+            Format::Latex    => tables.into_iter().map(|table| format!("\\[\n{table}\\]\n")).collect::<Vec<_>>().join("\n"),
+        })
+    }
+}
+
+/// `value` rounded to `precision` decimal places, without trailing zeros nor the sign of `-0`
+fn round(value: Scalor, precision: usize) -> String {
+    let rounded = format!("{value:.precision$}");
+    let trimmed = match rounded.contains('.') {
+        true  => rounded.trim_end_matches('0').trim_end_matches('.'),
+        false => &rounded,
+    };
+    match trimmed {
+        "-0" => "0".to_string(),
+        _    => trimmed.to_string(),
+    }
+}
+
+fn latex_variable(variable: &Variable) -> String {
+    fn name(name: &str) -> String {
+        let body = name.trim_end_matches(|c: char| c.is_ascii_digit());
+        let index = &name[body.len()..];
+        if !body.is_empty() && body.chars().all(|c| c.is_ascii_alphabetic()) {
+            if index.is_empty() {body.to_string()} else {format!("{body}_{{{index}}}")}
+        } else {
+            let escaped = name.chars().map(|c| match c {
+                '#' | '$' | '%' | '&' | '_' | '{' | '}' => format!("\\{c}"),
+                '\\' => "\\textbackslash{}".to_string(),
+                '^'  => "\\^{}".to_string(),
+                '~'  => "\\~{}".to_string(),
+                c    => c.to_string(),
+            }).collect::<String>();
+            format!("\\text{{{escaped}}}")
+        }
+    }
+
+    match variable {
+        Variable::Normal     { name: n } => name(n),
+        Variable::Negative   { name: n } => format!("{}^{{-}}", name(n)),
+        Variable::Slack      { id }      => format!("s_{{{id}}}"),
+        Variable::Surplus    { id }      => format!("e_{{{id}}}"),
+        Variable::Artificial { id }      => format!("a_{{{id}}}"),
+        Variable::Object                 => "z".to_string(),
+    }
+}
@@ -0,0 +1,106 @@
+use crate::error::SolveError;
+use crate::options::SolverOptions;
+use crate::problem::{Constraint, Sign};
+use crate::components::{variable::Variable, scalor::Scalor, range::Range, term::Term};
+use super::{Table, Tableau, BaseVariable, Solution, Progress};
+
+
+/// Rows added to an optimal table and reoptimization from its basis, for warm starts
+impl Table {
+    /// Add `constraint` on the problem's variables as new rows: a `≤` row with a new slack variable
+    /// as its base variable, a `≥` row multiplied by -1 as a `≤` one, and a `=` row as the both.
+    /// The basic solution may get infeasible, but the simplex criterions are kept. See `reoptimize`.
+    ///
+    /// Like cuts, the rows have no duals nor sensitivity in `Solution`.
+    pub fn add_constraint(&mut self, constraint: Constraint) -> Result<(), SolveError> {
+        let Constraint { lhs, sign, mut rhs } = constraint;
+
+        let mut row = vec![0.; self.variables.len()];
+        for Term { coefficient, variable } in lhs.terms {
+            let (constant, columns) = self.substitution.expression(&variable);
+            rhs -= coefficient * constant;
+            for (column, c) in columns {
+                let j = self.variables.iter().position(|v| v == &column)
+                    .filter(|_| column.is_structural())
+                    .ok_or(SolveError::UnknownVariable { variable: variable.clone() })?;
+                row[j] += coefficient * c;
+            }
+        }
+
+        if matches!(sign, Sign::LE | Sign::EQ) {
+            self.add_row(row.clone(), rhs);
+        }
+        if matches!(sign, Sign::GE | Sign::EQ) {
+            self.add_row(row.into_iter().map(|a| -a).collect(), -rhs);
+        }
+        Ok(())
+    }
+
+    /// Add rows restricting `variable` into `range` in addition to its current bounds,
+    /// one for each finite end of `range`: e.g. a branch of branch-and-bound. See `add_constraint`.
+    pub fn tighten_bound(&mut self, variable: Variable, range: Range) -> Result<(), SolveError> {
+        let Range { lower, upper } = range;
+        if upper.is_finite() {
+            self.add_constraint(Constraint::le(Term { coefficient: 1., variable: variable.clone() }, upper))?;
+        }
+        if lower.is_finite() {
+            self.add_constraint(Constraint::ge(Term { coefficient: 1., variable }, lower))?;
+        }
+        Ok(())
+    }
+
+    /// Solve from the current basis with `options.algorithm` ignored, and return the solution.
+    ///
+    /// When some base variable is negative, as after `add_constraint` or `tighten_bound` on an optimal table,
+    /// dual simplex method makes the basic solution feasible keeping the simplex criterions nonnegative,
+    /// usually in far fewer pivot operations than solving from scratch.
+    /// Then, or when all base variables are nonnegative, two-phase primal simplex method optimizes the table.
+    ///
+    /// `SolveError::Infeasible` when an added row can't be satisfied, and `SolveError::NotDualFeasible`
+    /// when some base variable is negative but the table wasn't optimal before the rows were added.
+    pub fn reoptimize(&mut self, options: &SolverOptions) -> Result<Solution, SolveError> {
+        let mut progress = Progress::new(self, options);
+
+        if self.bases[..(self.bases.len() - 1)].iter().any(|base| base.value < -options.feasibility_tolerance) {
+            let artificial = self.bases[..(self.bases.len() - 1)].iter()
+                .any(|base| base.variable.is_artificial() && base.value > options.feasibility_tolerance);
+            if artificial || !self.is_optimal(options) {
+                return Err(SolveError::NotDualFeasible)
+            }
+            self.iterate_dual(options, &mut progress)?;
+        } else if self.bases.iter().any(|base| base.variable.is_artificial() && base.value > options.feasibility_tolerance) {
+            self.phase_one(options, &mut progress)?;
+        }
+        self.iterate(options, &mut progress)?;
+
+        Ok(self.solution(options))
+    }
+}
+
+impl Table {
+    /// Add the row `Σ coefficients_j x_j ≤ rhs` just before the objective row, with a new slack variable
+    /// as its base variable. The row is written in the nonbasic variables by subtracting
+    /// the base variables' rows, so the slack variable's value is `rhs` minus the left-hand side
+    /// at the current basic solution, which may be negative. Missing coefficients of the last columns are 0.
+    pub(super) fn add_row(&mut self, mut coefficients: Vec<Scalor>, mut rhs: Scalor) {
+        coefficients.resize(self.variables.len(), 0.);
+        for (i, column) in self.base_columns().into_iter().enumerate() {
+            let rate = coefficients[column];
+            if rate == 0. {continue}
+
+            rhs -= self.bases[i].value * rate;
+            for (c, a) in coefficients.iter_mut().zip(&self.coefficients[i]) {
+                *c -= a * rate;
+            }
+        }
+
+        /* The new row is just before the objective row */
+        let (row, id) = (self.bases.len() - 1, self.bases.len());
+        self.coefficients.push_column(vec![0.; self.coefficients.column_size]).unwrap();
+        self.variables.push(Variable::Slack { id });
+        self.objective.push(0.);
+        coefficients.push(1.);
+        self.coefficients.insert_row(row, coefficients).unwrap();
+        self.bases.insert(row, BaseVariable { variable: Variable::Slack { id }, value: rhs });
+    }
+}
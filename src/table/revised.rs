@@ -0,0 +1,246 @@
+use crate::error::SolveError;
+use crate::options::{SolverOptions, PivotRule};
+use std::borrow::Cow;
+use crate::problem::{Sense, Substitution};
+use crate::components::{variable::Variable, scalor::Scalor, matrix::Matrix, factorization::Factorization};
+use super::{Table, Tableau, BaseVariable, RowOrigin, Progress, StandardForm};
+
+
+/// Current basis in revised simplex method
+pub(super) struct Basis {
+    columns:       Vec<usize>,   // This's index is just the row number of the table
+    values:        Vec<Scalor>,  // This's index is just the row number of the table
+    factorization: Factorization,
+}
+
+impl Basis {
+    /// Factorize the basis of `columns`, which may be reordered: the factorization decides the row of each column
+    fn new(standard: &StandardForm, columns: Vec<usize>, options: &SolverOptions) -> Result<Self, SolveError> {
+        let base_columns = columns.iter()
+            .map(|&j| standard.columns.column(j).unwrap().collect())
+            .collect::<Vec<_>>();
+        let (factorization, rows) = Factorization::new(&base_columns, options.pivot_tolerance)
+            .ok_or(SolveError::NumericalTrouble)?;
+
+        let mut ordered = vec![0; columns.len()];
+        for (j, row) in columns.into_iter().zip(rows) {
+            ordered[row] = j;
+        }
+        let values = factorization.ftran(&standard.rhs);
+        Ok(Self { columns: ordered, values, factorization })
+    }
+
+    /// Let column `column`, where `alpha = B⁻¹ a`, enter the basis at row `row`.
+    /// The factorization is refactorized after `options.refactorization_interval` updates.
+    fn pivot(&mut self, row: usize, column: usize, alpha: Vec<Scalor>, standard: &StandardForm, options: &SolverOptions) -> Result<(), SolveError> {
+        let theta = self.values[row] / alpha[row];
+        for (value, a) in self.values.iter_mut().zip(&alpha) {
+            *value -= theta * a;
+        }
+        self.values[row] = theta;
+        self.columns[row] = column;
+        self.factorization.update(row, alpha);
+
+        if self.factorization.updates() >= options.refactorization_interval {
+            *self = Basis::new(standard, std::mem::take(&mut self.columns), options)?;
+        }
+        Ok(())
+    }
+
+    /// `- Σ objective_B x_B` as the value in the objective row of a table
+    fn object_value(&self, objective: &[Scalor]) -> Scalor {
+        -self.columns.iter().zip(&self.values).map(|(&j, value)| objective[j] * value).sum::<Scalor>()
+    }
+
+    /// `objective - (objective_B B⁻¹) A`: the objective row of the table of this basis
+    fn criterions(&self, objective: &[Scalor], standard: &StandardForm) -> Vec<Scalor> {
+        let base_objective = self.columns.iter().map(|&j| objective[j]).collect::<Vec<_>>();
+        let y = self.factorization.btran(&base_objective);
+        objective.iter().enumerate()
+            .map(|(j, c)| c - standard.dot(&y, j))
+            .collect()
+    }
+}
+
+impl StandardForm {
+    /// Revised simplex method: the same two phases as `Table::solve_with_cuts`, keeping this sparse standard form
+    /// untouched. Each iteration computes simplex criterions through `btran` and products with the sparse columns,
+    /// and only the pivot column through `ftran` by the sparse LU factorization of the basis,
+    /// which is updated in product form by each pivot operation and refactorized periodically.
+    /// 
+    /// Ties of the ratio test are broken as `PivotRule::Bland` also for `PivotRule::Lexicographic`.
+    /// Returns the optimal basis. See `Table::from_basis`.
+    pub(super) fn solve_revised(&self, options: &SolverOptions, progress: &mut Progress) -> Result<Basis, SolveError> {
+        let rows = self.rhs.len();
+        let mut basis = Basis::new(self, self.origins.iter().map(|origin| origin.unit_column).collect(), options)?;
+
+        if basis.columns.iter().any(|&j| self.variables[j].is_artificial()) {
+            let artificial_sum = self.variables.iter()
+                .map(|variable| if variable.is_artificial() {1.} else {0.})
+                .collect::<Vec<_>>();
+            self.revise(&artificial_sum, &mut basis, options, progress)?;
+
+            if basis.object_value(&artificial_sum) < -options.feasibility_tolerance {
+                return Err(SolveError::Infeasible)
+            }
+
+            /* An artificial variable that can't be driven out stays in the basis at value 0.
+               Refactorization may reorder the rows, so search them again after each pivot operation. */
+            let mut stuck = Vec::new();
+            while let Some(row) = (0..rows).find(|&i|
+                self.variables[basis.columns[i]].is_artificial() && !stuck.contains(&basis.columns[i])
+            ) {
+                let mut unit = vec![0.; rows];
+                unit[row] = 1.;
+                let base_row = basis.factorization.btran(&unit);
+                if let Some(column) = (0..self.variables.len()).find(|&j|
+                    !self.variables[j].is_artificial() && self.dot(&base_row, j).abs() > options.pivot_tolerance
+                ) {
+                    let alpha = basis.factorization.ftran(&self.dense_column(column));
+                    basis.pivot(row, column, alpha, self, options)?;
+                } else {
+                    stuck.push(basis.columns[row]);
+                }
+            }
+        }
+
+        self.revise(&self.initial_criterions(), &mut basis, options, progress)?;
+        Ok(basis)
+    }
+
+    /// `-objective`: the objective row of the initial table
+    fn initial_criterions(&self) -> Vec<Scalor> {
+        self.objective.iter().map(|c| -c).collect()
+    }
+
+    /// Repeat pivot operations of revised simplex method until `basis` gets optimal for `objective`,
+    /// the objective row of the initial table
+    fn revise(&self, objective: &[Scalor], basis: &mut Basis, options: &SolverOptions, progress: &mut Progress) -> Result<(), SolveError> {
+        let mut rule = options.pivot_rule;
+        let mut degenerate_count = 0;
+
+        loop {
+            let criterions = basis.criterions(objective, self);
+            let mut candidate_columns = criterions.iter().enumerate()
+                .filter(|(j, c)| !self.variables[*j].is_artificial() && **c < -options.optimality_tolerance);
+            let column = match rule {
+                PivotRule::Bland => candidate_columns.next(),
+                PivotRule::Dantzig | PivotRule::Lexicographic => candidate_columns
+                    .reduce(|(min_criterion_column, min), (j, c)| {
+                        if c < min {(j, c)} else {(min_criterion_column, min)}
+                    }),
+            };
+            let Some((column, _)) = column else {return Ok(())};
+
+            progress.check(options)?;
+
+            let alpha = basis.factorization.ftran(&self.dense_column(column));
+            let max_increases = (0..alpha.len())
+                .filter(|&i| alpha[i] > options.pivot_tolerance)
+                .map(|i| (i, basis.values[i] / alpha[i]))
+                .collect::<Vec<_>>();
+            let min_maxinc = max_increases.iter()
+                .map(|(_, maxinc)| *maxinc)
+                .reduce(Scalor::min).ok_or(SolveError::Unbounded)?;
+            let mut candidate_rows = max_increases.into_iter()
+                .filter(|(_, maxinc)| *maxinc <= min_maxinc + options.feasibility_tolerance)
+                .map(|(i, _)| i);
+            let row = match rule {
+                PivotRule::Dantzig => candidate_rows.next(),
+                PivotRule::Bland | PivotRule::Lexicographic => candidate_rows.min_by_key(|&i| basis.columns[i]),
+            }.unwrap();
+
+            if basis.values[row] <= options.feasibility_tolerance {
+                degenerate_count += 1;
+                if degenerate_count >= options.stall_limit {rule = PivotRule::Bland}
+            } else {
+                degenerate_count = 0;
+            }
+
+            basis.pivot(row, column, alpha, self, options)?;
+
+            if basis.values.iter().any(|value| !value.is_finite()) {
+                return Err(SolveError::NumericalTrouble)
+            }
+
+            progress.iterations += 1;
+        }
+    }
+}
+
+impl Table {
+    /// The table of `basis` found by `StandardForm::solve_revised`.
+    /// Besides `Table::from_standard_form`, this's the only place where the sparse standard form gets dense:
+    /// each column is computed by `ftran` of the basis.
+    pub(super) fn from_basis(standard: StandardForm, basis: &Basis) -> Self {
+        let objective = standard.initial_criterions();
+        let constant = standard.constant();
+
+        let mut coefficients = Matrix::zeroed(standard.variables.len(), standard.rhs.len() + 1);
+        for j in 0..standard.variables.len() {
+            for (i, c) in basis.factorization.ftran(&standard.dense_column(j)).into_iter().enumerate() {
+                coefficients[i][j] = c;
+            }
+        }
+        *coefficients.rows.last_mut().unwrap() = basis.criterions(&objective, &standard);
+
+        let bases = basis.columns.iter().zip(&basis.values)
+            .map(|(&j, &value)| BaseVariable { variable: standard.variables[j].clone(), value })
+            .chain([BaseVariable { variable: Variable::Object, value: constant + basis.object_value(&objective) }])
+            .collect();
+
+        let StandardForm { sense, variables, origins, objective, substitution, .. } = standard;
+        Self { sense, variables, bases, coefficients, origins, objective, substitution }
+    }
+}
+
+/// The table of an optimal basis found by `StandardForm::solve_revised`, without the table itself:
+/// only the objective row is computed beforehand, and rows and columns through `btran` and `ftran` on demand
+pub(super) struct RevisedTableau<'a> {
+    standard:   &'a StandardForm,
+    basis:      &'a Basis,
+    criterions: Vec<Scalor>,
+} impl<'a> RevisedTableau<'a> {
+    pub(super) fn new(standard: &'a StandardForm, basis: &'a Basis) -> Self {
+        let criterions = basis.criterions(&standard.initial_criterions(), standard);
+        Self { standard, basis, criterions }
+    }
+}
+
+const _: () = {
+    impl Tableau for RevisedTableau<'_> {
+        fn sense(&self) -> Sense {
+            self.standard.sense
+        }
+        fn variables(&self) -> &[Variable] {
+            &self.standard.variables
+        }
+        fn origins(&self) -> &[RowOrigin] {
+            &self.standard.origins
+        }
+        fn objective(&self) -> &[Scalor] {
+            &self.standard.objective
+        }
+        fn substitution(&self) -> &Substitution {
+            &self.standard.substitution
+        }
+        fn base_values(&self) -> Vec<(usize, Scalor)> {
+            self.basis.columns.iter().copied().zip(self.basis.values.iter().copied()).collect()
+        }
+        fn criterions(&self) -> &[Scalor] {
+            &self.criterions
+        }
+        fn object_value(&self) -> Scalor {
+            self.standard.constant() + self.basis.object_value(&self.standard.initial_criterions())
+        }
+        fn row(&self, row: usize) -> Cow<'_, [Scalor]> {
+            let mut unit = vec![0.; self.standard.rhs.len()];
+            unit[row] = 1.;
+            let base_row = self.basis.factorization.btran(&unit);
+            Cow::Owned((0..self.standard.variables.len()).map(|j| self.standard.dot(&base_row, j)).collect())
+        }
+        fn column(&self, column: usize) -> Cow<'_, [Scalor]> {
+            Cow::Owned(self.basis.factorization.ftran(&self.standard.dense_column(column)))
+        }
+    }
+};
@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+use crate::problem::Sense;
+use crate::options::SolverOptions;
+use crate::components::{variable::Variable, scalor::Scalor, range::Range};
+use super::{Tableau, RowOrigin};
+
+
+/// Ranges over which each objective coefficient and each right-hand side can vary
+/// without changing the optimal basis, the other data fixed.
+/// 
+/// When the optimal solution is degenerate, these ranges may be narrower than
+/// the ones where the optimal *value's* formula holds.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SensitivityReport {
+    #[cfg_attr(feature = "serde", serde(with = "crate::components::variable::map"))]
+    pub objective_ranges: HashMap<Variable, Range>,
+    /// This's index is just the row number of the constraints
+    pub rhs_ranges:       Vec<Range>,
+}
+
+/// Ranging on an optimal basis
+pub(super) fn ranging<T: Tableau + ?Sized>(tableau: &T, options: &SolverOptions) -> SensitivityReport {
+    let (variables, objective, criterions) = (tableau.variables(), tableau.objective(), tableau.criterions());
+    let base_values  = tableau.base_values();
+    let base_columns = base_values.iter().map(|(column, _)| *column).collect::<Vec<_>>();
+
+    let objective_ranges = variables.iter().enumerate()
+        .filter(|(_, variable)| variable.is_structural())
+        .map(|(j, variable)| {
+            /* How much `objective[j]` can decrease and increase keeping all criterions nonnegative */
+            let (decrease, increase) = match base_columns.iter().position(|&column| column == j) {
+                None    => (Scalor::INFINITY, criterions[j].max(0.)),
+                Some(p) => {
+                    let row = tableau.row(p);
+                    let (mut decrease, mut increase) = (Scalor::INFINITY, Scalor::INFINITY);
+                    for k in (0..variables.len()).filter(|k| !base_columns.contains(k) && !variables[*k].is_artificial()) {
+                        let (a, criterion) = (row[k], criterions[k].max(0.));
+                        if a >  options.pivot_tolerance {decrease = decrease.min(criterion /  a)}
+                        if a < -options.pivot_tolerance {increase = increase.min(criterion / -a)}
+                    }
+                    (decrease, increase)
+                }
+            };
+            let range = match tableau.sense() {
+                Sense::Maximize => Range { lower:  objective[j] - decrease, upper:  objective[j] + increase },
+                Sense::Minimize => Range { lower: -objective[j] - increase, upper: -objective[j] + decrease },
+            };
+            (variable.clone(), range)
+        }).collect();
+
+    let rhs_ranges = tableau.origins().iter()
+        .map(|RowOrigin { unit_column, flipped, rhs }| {
+            /* How much the flipped right-hand side can decrease and increase keeping all base values nonnegative.
+               Base values move along the column of the inverse of basis. */
+            let column = tableau.column(*unit_column);
+            let (mut decrease, mut increase) = (Scalor::INFINITY, Scalor::INFINITY);
+            for (p, &(base, value)) in base_values.iter().enumerate() {
+                let (u, value) = (column[p], value.max(0.));
+                if variables[base].is_artificial() {
+                    /* An artificial variable left in the basis must stay 0 */
+                    if u.abs() > options.pivot_tolerance {(decrease, increase) = (0., 0.)}
+                    continue
+                }
+                if u >  options.pivot_tolerance {decrease = decrease.min(value /  u)}
+                if u < -options.pivot_tolerance {increase = increase.min(value / -u)}
+            }
+            if *flipped {(decrease, increase) = (increase, decrease)}
+            Range { lower: rhs - decrease, upper: rhs + increase }
+        }).collect();
+
+    SensitivityReport { objective_ranges, rhs_ranges }
+}
@@ -0,0 +1,87 @@
+use crate::problem::{Problem, Condition, Sense, Substitution};
+use crate::components::{variable::Variable, scalor::Scalor, sparse::SparseMatrix};
+use super::RowOrigin;
+
+
+/// Constraint rows of the initial table, kept sparse: the standard form of the problem whose bounds are substituted,
+/// with the rows of negative right-hand side multiplied by -1 and an artificial column for each row
+/// that has no slack column of its unit vector. Slack, surplus and artificial columns have a single entry.
+pub(super) struct StandardForm {
+    pub(super) sense:        Sense,
+    pub(super) variables:    Vec<Variable>,   // This's index is just the column number of `columns`
+    pub(super) columns:      SparseMatrix,
+    pub(super) rhs:          Vec<Scalor>,     // Nonnegative
+    pub(super) origins:      Vec<RowOrigin>,  // `unit_column` of each is the column of the initial base variable
+    pub(super) objective:    Vec<Scalor>,     // Coefficients of the objective function in the sense of maximization, for each column
+    pub(super) substitution: Substitution,
+}
+
+impl StandardForm {
+    pub(super) fn new(problem: Problem) -> Self {
+        let (problem, substitution) = problem.substitute_bounds();
+        let Problem { sense, objective_function, condition, .. } = problem.into_standard_form();
+        let Condition { A: mut columns, x: mut variables, b: mut rhs, .. } = condition;
+
+        let flipped = rhs.iter().map(|value| *value < 0.).collect::<Vec<_>>();
+        columns.map_entries(|i, a| if flipped[i] {-a} else {a});
+
+        /* The first non-structural column that is the unit vector of each row */
+        let mut unit_columns = vec![None; rhs.len()];
+        for j in (0..variables.len()).rev().filter(|&j| !variables[j].is_structural()) {
+            let mut entries = columns.column(j).unwrap();
+            if let (Some((i, 1.)), None) = (entries.next(), entries.next()) {
+                unit_columns[i] = Some(j)
+            }
+        }
+
+        let mut origins = Vec::with_capacity(rhs.len());
+        for (i, value) in rhs.iter_mut().enumerate() {
+            origins.push(RowOrigin { unit_column: 0, flipped: flipped[i], rhs: *value });
+            if flipped[i] {*value = -*value}
+        }
+        for (i, origin) in origins.iter_mut().enumerate() {
+            origin.unit_column = match unit_columns[i] {
+                Some(j) => j,
+                None    => {
+                    columns.push_column([(i, 1.)]).unwrap();
+                    variables.push(Variable::Artificial { id: i + 1 });
+                    variables.len() - 1
+                }
+            };
+        }
+
+        /* Minimizing `c x` is maximizing `-c x` */
+        let objective = variables.iter()
+            .map(|var| objective_function.terms.iter()
+                .find(|term| &term.variable == var)
+                .map_or(0., |term| match sense {
+                    Sense::Maximize =>  term.coefficient,
+                    Sense::Minimize => -term.coefficient,
+                })
+            ).collect();
+
+        Self { sense, variables, columns, rhs, origins, objective, substitution }
+    }
+
+    /// The constant of the objective function in the sense of maximization,
+    /// which is the value of the objective row in the initial table
+    pub(super) fn constant(&self) -> Scalor {
+        match self.sense {
+            Sense::Maximize =>  self.substitution.constant(),
+            Sense::Minimize => -self.substitution.constant(),
+        }
+    }
+
+    pub(super) fn dense_column(&self, index: usize) -> Vec<Scalor> {
+        let mut column = vec![0.; self.rhs.len()];
+        for (i, value) in self.columns.column(index).unwrap() {
+            column[i] = value
+        }
+        column
+    }
+
+    /// `y a_j`
+    pub(super) fn dot(&self, y: &[Scalor], index: usize) -> Scalor {
+        self.columns.column(index).unwrap().map(|(i, value)| y[i] * value).sum()
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use crate::error::SolveError;
+use crate::options::SolverOptions;
+use crate::components::{variable::Variable, scalor::Scalor};
+use super::{Table, Tableau, Pivot, Progress, Iteration};
+
+
+/// Phase of two-phase simplex method
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Phase {
+    /// Maximizing `- Σ artificial variables` to find a feasible basis
+    One,
+    /// Optimizing the objective function from a feasible basis
+    Two,
+}
+
+/// A pivot operation and the table after it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub phase:           Phase,
+    pub entering:        Variable,
+    pub leaving:         Variable,
+    /// Position in the table before the pivot operation, and the value there
+    pub pivot:           Pivot,
+    /// The value of the objective function in the sense of the problem after the pivot operation.
+    /// In `Phase::One`, the value of `- Σ artificial variables` instead.
+    pub objective_value: Scalor,
+    /// Values of the problem's variables at the basic solution after the pivot operation,
+    /// with the bounds substituted by `Table::new` restored
+    pub variables:       HashMap<Variable, Scalor>,
+    pub table:           Table,
+}
+
+/// Iterator of pivot operations of two-phase primal simplex method, made by `Table::steps`.
+/// 
+/// Ends when the table gets optimal, or just after yielding an error.
+/// Phase I is finished as soon as it gets optimal: pivot operations driving artificial variables
+/// out of the basis and restoring the objective row are not yielded. So `table()` is always
+/// the one that the next step's pivot is on.
+pub struct SimplexSteps {
+    table:     Table,
+    options:   SolverOptions,
+    progress:  Progress,
+    iteration: Iteration,
+    phase:     Phase,
+    // The objective row saved through Phase I
+    objective: Option<(Vec<Scalor>, Scalor)>,
+    // An error of finishing Phase I, yielded by the next `next`
+    error:     Option<SolveError>,
+    done:      bool,
+}
+
+impl Table {
+    /// Solve step by step. `options.algorithm` is ignored: always on this table by two-phase primal simplex method.
+    pub fn steps(mut self, options: SolverOptions) -> SimplexSteps {
+        #[cfg(test)] println!("{self:?}");
+
+        let (phase, objective) = match self.bases.iter().any(|base| base.variable.is_artificial()) {
+            true  => (Phase::One, Some(self.start_phase_one())),
+            false => (Phase::Two, None),
+        };
+        let mut steps = SimplexSteps {
+            progress:  Progress::new(&self, &options),
+            iteration: Iteration::new(&self, &options),
+            table: self,
+            options,
+            phase,
+            objective,
+            error: None,
+            done:  false,
+        };
+        steps.finish_phase_one_if_optimal();
+        steps
+    }
+}
+
+impl SimplexSteps {
+    /// The current table
+    pub fn table(&self) -> &Table {
+        &self.table
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    fn finish_phase_one_if_optimal(&mut self) {
+        if self.phase != Phase::One || !self.table.is_optimal(&self.options) {return}
+
+        let objective = self.objective.take().unwrap(/* saved at the start of Phase I */);
+        match self.table.finish_phase_one(objective, &self.options) {
+            Ok(()) => {
+                self.phase     = Phase::Two;
+                self.iteration = Iteration::new(&self.table, &self.options);
+            }
+            Err(error) => self.error = Some(error),
+        }
+    }
+}
+
+impl Iterator for SimplexSteps {
+    type Item = Result<Step, SolveError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {return None}
+        if let Some(error) = self.error.take() {
+            self.done = true;
+            return Some(Err(error))
+        }
+
+        match self.table.step(&mut self.iteration, &self.options, &mut self.progress) {
+            Ok(Some((pivot, leaving))) => {
+                let mut variables = self.table.structural_values();
+                self.table.substitution.restore_values(&mut variables);
+                let step = Step {
+                    phase:           self.phase,
+                    entering:        self.table.variables[pivot.column].clone(),
+                    leaving,
+                    pivot,
+                    objective_value: match self.phase {
+                        Phase::One => self.table.object_value(),
+                        Phase::Two => self.table.objective_value(),
+                    },
+                    variables,
+                    table:           self.table.clone(),
+                };
+                self.finish_phase_one_if_optimal();
+                Some(Ok(step))
+            }
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(error) => {
+                self.done = true;
+                Some(Err(error))
+            }
+        }
+    }
+}
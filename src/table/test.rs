@@ -1,7 +1,7 @@
 #![allow(unused_imports)]
-use std::collections::HashMap;
-use crate::{Problem, Condition, var, matrix, components::variable::{slack, Variable}, problem::Sign};
-use super::{Table, BaseVariable, Pivot, Solution};
+use std::{collections::HashMap, time::Duration};
+use crate::{Problem, Condition, Constraint, SolveError, PivotError, SolverOptions, PivotRule, Algorithm, var, matrix, components::{scalor::Scalor, range::Range, variable::{slack, surplus, artificial, Variable}}, problem::{Sense, Sign, Substitution}};
+use super::{Table, BaseVariable, Pivot, Phase, RowOrigin, Solution, SensitivityReport};
 
 
 #[test] fn test_simplex_method() {
@@ -27,13 +27,16 @@ use super::{Table, BaseVariable, Pivot, Solution};
         )
     ).into_standard_form();
     assert_eq!(problem, Problem {
+        sense: Sense::Maximize,
         objective_function: 4*var("x1") + 5*var("x2"),
+        bounds: HashMap::new(),
+        kinds: HashMap::new(),
         condition: Condition {
             A: matrix! {
                 2, 5, 1, 0, 0
                 6, 4, 0, 1, 0
                 3, 1, 0, 0, 1
-            },
+            }.into(),
             x: vec![
                 var("x1"),
                 var("x2"),
@@ -42,19 +45,21 @@ use super::{Table, BaseVariable, Pivot, Solution};
                 slack(3),
             ],
 
-            sign: Sign::EQ,
+            signs: vec![Sign::EQ; 3],
 
             b: vec![
                 20.,
                 27.,
                 12.,
-            ]
+            ],
+            names: Vec::new(),
         }
     });
 
     let table = Table::from_problem(problem.into_standard_form());
 
     assert_eq!(table, Table {
+        sense: Sense::Maximize,
         variables: vec![
             var("x1"),
             var("x2"),
@@ -74,13 +79,20 @@ use super::{Table, BaseVariable, Pivot, Solution};
              3,  1, 0, 0, 1
             -4, -5, 0, 0, 0
         },
+        origins: vec![
+            RowOrigin { unit_column:2, flipped:false, rhs:20. },
+            RowOrigin { unit_column:3, flipped:false, rhs:27. },
+            RowOrigin { unit_column:4, flipped:false, rhs:12. },
+        ],
+        objective: vec![4., 5., 0., 0., 0.],
+        substitution: Substitution::default(),
     });
-    assert_eq!(table.is_optimal(), false);
-    assert_eq!(table.pivot(), Pivot {
+    assert!(!table.is_optimal(&SolverOptions::default()));
+    assert_eq!(table.pivot(&SolverOptions::default()), Ok(Some(Pivot {
         row:    0,
         column: 1,
         value:  5.,
-    });
+    })));
 
     assert_eq!(table.solve().unwrap(), Solution {
         variables:     HashMap::from([
@@ -88,6 +100,26 @@ use super::{Table, BaseVariable, Pivot, Solution};
             (var("x2"), 3.),
         ]),
         optimal_value: 25.,
+        duals:         vec![7./11., 5./11., 0.],
+        reduced_costs: HashMap::from([
+            (var("x1"), 0.),
+            (var("x2"), 0.),
+        ]),
+        slacks:        vec![0., 0., 1.5],
+        activities:    vec![20., 27., 10.5],
+        binding:       vec![true, true, false],
+        mip_gap:       None,
+        sensitivity:   SensitivityReport {
+            objective_ranges: HashMap::from([
+                (var("x1"), Range { lower:2., upper:7.5 }),
+                (var("x2"), Range { lower:2.666666666666667, upper:10. }),
+            ]),
+            rhs_ranges: vec![
+                Range { lower:14.5, upper:33.75 },
+                Range { lower:16., upper:384./13. },
+                Range { lower:10.5, upper:Scalor::INFINITY },
+            ],
+        },
     });
 }
 
@@ -120,6 +152,26 @@ use super::{Table, BaseVariable, Pivot, Solution};
                 (var("x2"), 3.),
             ]),
             optimal_value: 25.,
+            duals:         vec![7./11., 5./11., 0.],
+            reduced_costs: HashMap::from([
+                (var("x1"), 0.),
+                (var("x2"), 0.),
+            ]),
+            slacks:        vec![0., 0., 1.5],
+            activities:    vec![20., 27., 10.5],
+            binding:       vec![true, true, false],
+            mip_gap:       None,
+            sensitivity:   SensitivityReport {
+                objective_ranges: HashMap::from([
+                    (var("x1"), Range { lower:2., upper:7.5 }),
+                    (var("x2"), Range { lower:2.666666666666667, upper:10. }),
+                ]),
+                rhs_ranges: vec![
+                    Range { lower:14.5, upper:33.75 },
+                    Range { lower:16., upper:384./13. },
+                    Range { lower:10.5, upper:Scalor::INFINITY },
+                ],
+            },
         }
     );
 
@@ -151,7 +203,1051 @@ use super::{Table, BaseVariable, Pivot, Solution};
                 (var("x2"), 300.),
             ]),
             optimal_value: 13000.,
+            duals:         vec![5., 5., 0.],
+            reduced_costs: HashMap::from([
+                (var("x1"), 0.),
+                (var("x2"), 0.),
+            ]),
+            slacks:        vec![0., 0., 600.],
+            activities:    vec![800., 1800., 900.],
+            binding:       vec![true, true, false],
+            mip_gap:       None,
+            sensitivity:   SensitivityReport {
+                objective_ranges: HashMap::from([
+                    (var("x1"), Range { lower:15., upper:22.5 }),
+                    (var("x2"), Range { lower:80./3., upper:40. }),
+                ]),
+                rhs_ranges: vec![
+                    Range { lower:2000./3., upper:900. },
+                    Range { lower:1600., upper:2040. },
+                    Range { lower:900., upper:Scalor::INFINITY },
+                ],
+            },
+        }
+    );
+
+}
+
+#[test] fn test_two_phase() {
+    let problem = Problem::maximize(2*var("x1") + 3*var("x2"),
+        Condition::each_le(
+            matrix! {
+                 1,  1
+                -1,  0
+                 0, -1
+            },
+            vec![
+                var("x1"),
+                var("x2"),
+            ],
+
+            /* less than or equals to */
+
+            vec![
+                 4,
+                -1,
+                -1,
+            ]
+        )
+    );
+
+    let table = Table::from_problem(problem);
+    assert_eq!(table, Table {
+        sense: Sense::Maximize,
+        variables: vec![
+            var("x1"),
+            var("x2"),
+            slack(1),
+            slack(2),
+            slack(3),
+            artificial(2),
+            artificial(3),
+        ],
+        bases: vec![
+            BaseVariable { variable:slack(1), value:4. },
+            BaseVariable { variable:artificial(2), value:1. },
+            BaseVariable { variable:artificial(3), value:1. },
+            BaseVariable { variable:Variable::Object, value:0. },
+        ],
+        coefficients: matrix! {
+             1,  1, 1,  0,  0, 0, 0
+             1,  0, 0, -1,  0, 1, 0
+             0,  1, 0,  0, -1, 0, 1
+            -2, -3, 0,  0,  0, 0, 0
+        },
+        origins: vec![
+            RowOrigin { unit_column:2, flipped:false, rhs:4. },
+            RowOrigin { unit_column:5, flipped:true, rhs:-1. },
+            RowOrigin { unit_column:6, flipped:true, rhs:-1. },
+        ],
+        objective: vec![2., 3., 0., 0., 0., 0., 0.],
+        substitution: Substitution::default(),
+    });
+
+    assert_eq!(table.solve().unwrap(), Solution {
+        variables:     HashMap::from([
+            (var("x1"), 1.),
+            (var("x2"), 3.),
+        ]),
+        optimal_value: 11.,
+        duals:         vec![3., 1., 0.],
+        reduced_costs: HashMap::from([
+            (var("x1"), 0.),
+            (var("x2"), 0.),
+        ]),
+        slacks:        vec![0., 0., 2.],
+        activities:    vec![4., -1., -3.],
+        binding:       vec![true, true, false],
+        mip_gap:       None,
+        sensitivity:   SensitivityReport {
+            objective_ranges: HashMap::from([
+                (var("x1"), Range { lower:-Scalor::INFINITY, upper:3. }),
+                (var("x2"), Range { lower:2., upper:Scalor::INFINITY }),
+            ]),
+            rhs_ranges: vec![
+                Range { lower:2., upper:Scalor::INFINITY },
+                Range { lower:-3., upper:0. },
+                Range { lower:-3., upper:Scalor::INFINITY },
+            ],
+        },
+    });
+
+    assert_eq!(
+        Problem::maximize(1*var("x1") + 1*var("x2"),
+            Condition::each_le(
+                matrix! {
+                     1,  1
+                    -1,  0
+                },
+                vec![
+                    var("x1"),
+                    var("x2"),
+                ],
+
+                /* less than or equals to */
+
+                vec![
+                     1,
+                    -2,
+                ]
+            )
+        ).solve(),
+        Err(SolveError::Infeasible)
+    );
+}
+
+#[test] fn test_mixed_signs() {
+    let problem = Problem::maximize(3*var("x1") + 2*var("x2"),
+        Condition::new(
+            matrix! {
+                1,  1
+                1,  3
+                1, -1
+            },
+            vec![
+                var("x1"),
+                var("x2"),
+            ],
+            vec![
+                Sign::LE,
+                Sign::GE,
+                Sign::EQ,
+            ],
+            vec![
+                4,
+                6,
+                0,
+            ]
+        )
+    );
+
+    let table = Table::from_problem(problem);
+    assert_eq!(table, Table {
+        sense: Sense::Maximize,
+        variables: vec![
+            var("x1"),
+            var("x2"),
+            slack(1),
+            surplus(2),
+            artificial(2),
+            artificial(3),
+        ],
+        bases: vec![
+            BaseVariable { variable:slack(1), value:4. },
+            BaseVariable { variable:artificial(2), value:6. },
+            BaseVariable { variable:artificial(3), value:0. },
+            BaseVariable { variable:Variable::Object, value:0. },
+        ],
+        coefficients: matrix! {
+             1,  1, 1,  0, 0, 0
+             1,  3, 0, -1, 1, 0
+             1, -1, 0,  0, 0, 1
+            -3, -2, 0,  0, 0, 0
+        },
+        origins: vec![
+            RowOrigin { unit_column:2, flipped:false, rhs:4. },
+            RowOrigin { unit_column:4, flipped:false, rhs:6. },
+            RowOrigin { unit_column:5, flipped:false, rhs:0. },
+        ],
+        objective: vec![3., 2., 0., 0., 0., 0.],
+        substitution: Substitution::default(),
+    });
+
+    assert_eq!(table.solve().unwrap(), Solution {
+        variables:     HashMap::from([
+            (var("x1"), 2.),
+            (var("x2"), 2.),
+        ]),
+        optimal_value: 10.,
+        duals:         vec![2.5, 0., 0.5],
+        reduced_costs: HashMap::from([
+            (var("x1"), 0.),
+            (var("x2"), 0.),
+        ]),
+        slacks:        vec![0., 2., 0.],
+        activities:    vec![4., 8., 0.],
+        binding:       vec![true, false, true],
+        mip_gap:       None,
+        sensitivity:   SensitivityReport {
+            objective_ranges: HashMap::from([
+                (var("x1"), Range { lower:-2., upper:Scalor::INFINITY }),
+                (var("x2"), Range { lower:-3., upper:Scalor::INFINITY }),
+            ]),
+            rhs_ranges: vec![
+                Range { lower:3., upper:Scalor::INFINITY },
+                Range { lower:-Scalor::INFINITY, upper:8. },
+                Range { lower:-4., upper:2. },
+            ],
+        },
+    });
+}
+
+#[test] fn test_minimize() {
+    assert_eq!(
+        Problem::minimize(2*var("x1") + 3*var("x2"),
+            Condition::each_ge(
+                matrix! {
+                    1, 1
+                    1, 2
+                },
+                vec![
+                    var("x1"),
+                    var("x2"),
+                ],
+
+                /* greater than or equals to */
+
+                vec![
+                    4,
+                    6,
+                ]
+            )
+        ).solve().unwrap(),
+        Solution {
+            variables:     HashMap::from([
+                (var("x1"), 2.),
+                (var("x2"), 2.),
+            ]),
+            optimal_value: 10.,
+            duals:         vec![1., 1.],
+            reduced_costs: HashMap::from([
+                (var("x1"), 0.),
+                (var("x2"), 0.),
+            ]),
+            slacks:        vec![0., 0.],
+            activities:    vec![4., 6.],
+            binding:       vec![true, true],
+            mip_gap:       None,
+            sensitivity:   SensitivityReport {
+                objective_ranges: HashMap::from([
+                    (var("x1"), Range { lower:1.5, upper:3. }),
+                    (var("x2"), Range { lower:2., upper:4. }),
+                ]),
+                rhs_ranges: vec![
+                    Range { lower:3., upper:6. },
+                    Range { lower:4., upper:8. },
+                ],
+            },
+        }
+    );
+}
+
+#[test] fn test_errors() {
+    assert_eq!(
+        Problem::maximize(1*var("x1") + 1*var("x2"),
+            Condition::each_le(
+                matrix! {
+                    1, -1
+                },
+                vec![
+                    var("x1"),
+                    var("x2"),
+                ],
+
+                /* less than or equals to */
+
+                vec![
+                    1,
+                ]
+            )
+        ).solve(),
+        Err(SolveError::Unbounded)
+    );
+
+    assert_eq!(
+        Problem::maximize(1*var("x1") + 1*var("x2"),
+            Condition::each_le(
+                matrix! {
+                    1, 1
+                    2, 1
+                },
+                vec![
+                    var("x1"),
+                    var("x2"),
+                ],
+
+                /* less than or equals to */
+
+                vec![
+                    1,
+                ]
+            )
+        ).solve(),
+        Err(SolveError::DimensionMismatch { name: "signs", expected: 2, found: 1 })
+    );
+}
+
+#[test] fn test_limits() {
+    /* Klee–Minty cube of dimension 3: Dantzig's rule visits all the 8 vertices */
+    let klee_minty = || Problem::maximize(4*var("x1") + 2*var("x2") + 1*var("x3"),
+        Condition::each_le(
+            matrix! {
+                1, 0, 0
+                4, 1, 0
+                8, 4, 1
+            },
+            vec![
+                var("x1"),
+                var("x2"),
+                var("x3"),
+            ],
+
+            /* less than or equals to */
+
+            vec![
+                5,
+                25,
+                125,
+            ]
+        )
+    );
+
+    assert_eq!(
+        klee_minty().solve().unwrap(),
+        Solution {
+            variables:     HashMap::from([
+                (var("x1"), 0.),
+                (var("x2"), 0.),
+                (var("x3"), 125.),
+            ]),
+            optimal_value: 125.,
+            duals:         vec![0., 0., 1.],
+            reduced_costs: HashMap::from([
+                (var("x1"), -4.),
+                (var("x2"), -2.),
+                (var("x3"), 0.),
+            ]),
+            slacks:        vec![5., 25., 0.],
+            activities:    vec![0., 0., 125.],
+            binding:       vec![false, false, true],
+            mip_gap:       None,
+            sensitivity:   SensitivityReport {
+                objective_ranges: HashMap::from([
+                    (var("x1"), Range { lower:-Scalor::INFINITY, upper:8. }),
+                    (var("x2"), Range { lower:-Scalor::INFINITY, upper:4. }),
+                    (var("x3"), Range { lower:0.5, upper:Scalor::INFINITY }),
+                ]),
+                rhs_ranges: vec![
+                    Range { lower:0., upper:Scalor::INFINITY },
+                    Range { lower:0., upper:Scalor::INFINITY },
+                    Range { lower:0., upper:Scalor::INFINITY },
+                ],
+            },
+        }
+    );
+    assert_eq!(
+        klee_minty().solve_with(SolverOptions { max_iterations: Some(7), ..Default::default() }).unwrap().optimal_value,
+        125.
+    );
+    assert_eq!(
+        klee_minty().solve_with(SolverOptions { max_iterations: Some(6), ..Default::default() }),
+        Err(SolveError::IterationLimit { iterations: 6 })
+    );
+    assert!(matches!(
+        klee_minty().solve_with(SolverOptions { time_limit: Some(Duration::ZERO), ..Default::default() }),
+        Err(SolveError::TimeLimit { .. })
+    ));
+}
+
+#[test] fn test_degenerate_problem() {
+    /* Beale's example, where Dantzig's rule cycles */
+    let beale = || Problem::maximize(0.75*var("x1") - 20*var("x2") + 0.5*var("x3") - 6*var("x4"),
+        Condition::each_le(
+            matrix! {
+                0.25, -8.,  -1.,  9.
+                0.5,  -12., -0.5, 3.
+                0.,    0.,   1.,  0.
+            },
+            vec![
+                var("x1"),
+                var("x2"),
+                var("x3"),
+                var("x4"),
+            ],
+
+            /* less than or equals to */
+
+            vec![
+                0,
+                0,
+                1,
+            ]
+        )
+    );
+
+    assert_eq!(
+        beale().solve_with(SolverOptions { stall_limit: usize::MAX, ..Default::default() }),
+        Err(SolveError::IterationLimit { iterations: 550 })
+    );
+    for options in [
+        SolverOptions::default(),
+        SolverOptions { pivot_rule: PivotRule::Bland,         ..Default::default() },
+        SolverOptions { pivot_rule: PivotRule::Lexicographic, ..Default::default() },
+    ] {
+        let optimal_value = beale().solve_with(options).unwrap().optimal_value;
+        assert!((optimal_value - 1.25).abs() < 1e-9);
+    }
+}
+
+#[test] fn test_dual_simplex() {
+    let problem = || Problem::minimize(3*var("x1") + 2*var("x2"),
+        Condition::each_ge(
+            matrix! {
+                1, 1
+                1, 3
+            },
+            vec![
+                var("x1"),
+                var("x2"),
+            ],
+
+            /* greater than or equals to */
+
+            vec![
+                4,
+                6,
+            ]
+        )
+    );
+
+    let mut table = Table::from_problem(problem());
+    assert!(table.start_dual(&SolverOptions::default()));
+    assert_eq!(table, Table {
+        sense: Sense::Minimize,
+        variables: vec![
+            var("x1"),
+            var("x2"),
+            surplus(1),
+            surplus(2),
+        ],
+        bases: vec![
+            BaseVariable { variable:surplus(1), value:-4. },
+            BaseVariable { variable:surplus(2), value:-6. },
+            BaseVariable { variable:Variable::Object, value:0. },
+        ],
+        coefficients: matrix! {
+            -1., -1., 1., 0.
+            -1., -3., 0., 1.
+             3.,  2., 0., 0.
+        },
+        origins: vec![
+            RowOrigin { unit_column:2, flipped:true, rhs:4. },
+            RowOrigin { unit_column:3, flipped:true, rhs:6. },
+        ],
+        objective: vec![-3., -2., 0., 0.],
+        substitution: Substitution::default(),
+    });
+
+    let dual = SolverOptions { algorithm: Algorithm::Dual, ..Default::default() };
+    let solution = problem().solve_with(dual).unwrap();
+    assert_eq!(solution.variables, HashMap::from([
+        (var("x1"), 0.),
+        (var("x2"), 4.),
+    ]));
+    assert_eq!(solution.optimal_value, 8.);
+    assert_eq!(solution.duals, vec![2., 0.]);
+    assert_eq!(solution.reduced_costs, HashMap::from([
+        (var("x1"), 1.),
+        (var("x2"), 0.),
+    ]));
+    assert_eq!(solution.slacks, vec![0., 6.]);
+
+    /* Not dual feasible: solved by two-phase simplex method */
+    let mut table = Table::from_problem(Problem::maximize(3*var("x1") + 2*var("x2"),
+        Condition::each_ge(
+            matrix! {
+                1, 1
+            },
+            vec![
+                var("x1"),
+                var("x2"),
+            ],
+
+            /* greater than or equals to */
+
+            vec![
+                4,
+            ]
+        )
+    ));
+    assert!(!table.start_dual(&dual));
+
+    assert_eq!(
+        Problem::minimize(1*var("x1"),
+            Condition::each_ge(
+                matrix! {
+                    -1
+                },
+                vec![
+                    var("x1"),
+                ],
+
+                /* greater than or equals to */
+
+                vec![
+                    1,
+                ]
+            )
+        ).solve_with(dual),
+        Err(SolveError::Infeasible)
+    );
+}
+
+#[test] fn test_revised_simplex() {
+    let problems = [
+        Problem::maximize(4*var("x1") + 5*var("x2"),
+            Condition::each_le(
+                matrix! {
+                    2, 5
+                    6, 4
+                    3, 1
+                },
+                vec![
+                    var("x1"),
+                    var("x2"),
+                ],
+
+                /* less than or equals to */
+
+                vec![
+                    20,
+                    27,
+                    12,
+                ]
+            )
+        ),
+        Problem::maximize(3*var("x1") + 2*var("x2"),
+            Condition::new(
+                matrix! {
+                    1,  1
+                    1,  3
+                    1, -1
+                },
+                vec![
+                    var("x1"),
+                    var("x2"),
+                ],
+                vec![
+                    Sign::LE,
+                    Sign::GE,
+                    Sign::EQ,
+                ],
+                vec![
+                    4,
+                    6,
+                    0,
+                ]
+            )
+        ),
+        Problem::minimize(3*var("x1") + 2*var("x2"),
+            Condition::each_ge(
+                matrix! {
+                    1, 1
+                    1, 3
+                },
+                vec![
+                    var("x1"),
+                    var("x2"),
+                ],
+
+                /* greater than or equals to */
+
+                vec![
+                    4,
+                    6,
+                ]
+            )
+        ),
+        /* The second row is redundant, whose artificial variable stays in the basis */
+        Problem::maximize(1*var("x1"),
+            Condition::each_eq(
+                matrix! {
+                    1, 1
+                    2, 2
+                },
+                vec![
+                    var("x1"),
+                    var("x2"),
+                ],
+
+                /* equals to */
+
+                vec![
+                    2,
+                    4,
+                ]
+            )
+        ),
+        Problem::maximize(0.75*var("x1") - 20*var("x2") + 0.5*var("x3") - 6*var("x4"),
+            Condition::each_le(
+                matrix! {
+                    0.25, -8.,  -1.,  9.
+                    0.5,  -12., -0.5, 3.
+                    0.,    0.,   1.,  0.
+                },
+                vec![
+                    var("x1"),
+                    var("x2"),
+                    var("x3"),
+                    var("x4"),
+                ],
+
+                /* less than or equals to */
+
+                vec![
+                    0,
+                    0,
+                    1,
+                ]
+            )
+        ),
+    ];
+
+    let close = |a: Scalor, b: Scalor| (a - b).abs() < 1e-9;
+    for refactorization_interval in [1, 50] {
+        let revised = SolverOptions { algorithm: Algorithm::Revised, refactorization_interval, ..Default::default() };
+        for problem in &problems {
+            let (solution, expected) = (problem.clone().solve_with(revised).unwrap(), problem.clone().solve().unwrap());
+            assert!(close(solution.optimal_value, expected.optimal_value));
+            for (variable, value) in &expected.variables {
+                assert!(close(solution.variables[variable], *value));
+            }
+            for (dual, expected_dual) in solution.duals.iter().zip(&expected.duals) {
+                assert!(close(*dual, *expected_dual));
+            }
+
+            /* Read from the basis without the table */
+            for (variable, reduced_cost) in &expected.reduced_costs {
+                assert!(close(solution.reduced_costs[variable], *reduced_cost));
+            }
+            for (values, expected_values) in [(&solution.slacks, &expected.slacks), (&solution.activities, &expected.activities)] {
+                assert!(values.iter().zip(expected_values).all(|(a, b)| close(*a, *b)));
+            }
+            assert_eq!(solution.binding, expected.binding);
+            let ranges = |Range { lower, upper }: Range, Range { lower: l, upper: u }: Range|
+                (lower == l || close(lower, l)) && (upper == u || close(upper, u));
+            for (variable, range) in &expected.sensitivity.objective_ranges {
+                assert!(ranges(solution.sensitivity.objective_ranges[variable], *range));
+            }
+            for (range, expected_range) in solution.sensitivity.rhs_ranges.iter().zip(&expected.sensitivity.rhs_ranges) {
+                assert!(ranges(*range, *expected_range));
+            }
         }
+    }
+
+    let revised = SolverOptions { algorithm: Algorithm::Revised, ..Default::default() };
+    assert_eq!(
+        Problem::maximize(1*var("x1") + 1*var("x2"),
+            Condition::each_le(
+                matrix! {
+                    1, -1
+                },
+                vec![
+                    var("x1"),
+                    var("x2"),
+                ],
+
+                /* less than or equals to */
+
+                vec![
+                    1,
+                ]
+            )
+        ).solve_with(revised),
+        Err(SolveError::Unbounded)
+    );
+    assert_eq!(
+        Problem::maximize(1*var("x1"),
+            Condition::new(
+                matrix! {
+                    1
+                    1
+                },
+                vec![
+                    var("x1"),
+                ],
+                vec![
+                    Sign::LE,
+                    Sign::GE,
+                ],
+                vec![
+                    1,
+                    2,
+                ]
+            )
+        ).solve_with(revised),
+        Err(SolveError::Infeasible)
+    );
+}
+
+#[test] fn test_steps() {
+    let problem = Problem::maximize(2*var("x1") + 3*var("x2"),
+        Condition::each_le(
+            matrix! {
+                 1,  1
+                -1,  0
+                 0, -1
+            },
+            vec![
+                var("x1"),
+                var("x2"),
+            ],
+
+            /* less than or equals to */
+
+            vec![
+                 4,
+                -1,
+                -1,
+            ]
+        )
+    );
+
+    let steps = problem.clone().steps(SolverOptions::default()).unwrap()
+        .collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(
+        steps.iter()
+            .map(|step| (step.phase, step.entering.clone(), step.leaving.clone(), step.pivot, step.objective_value))
+            .collect::<Vec<_>>(),
+        vec![
+            (Phase::One, var("x1"), artificial(2), Pivot { row:1, column:0, value:1. }, -1.),
+            (Phase::One, var("x2"), artificial(3), Pivot { row:2, column:1, value:1. },  0.),
+            (Phase::Two, slack(3),  slack(1),      Pivot { row:0, column:4, value:1. }, 11.),
+        ]
+    );
+    assert_eq!(steps.last().unwrap().table.clone().solve().unwrap().optimal_value, 11.);
+
+    let mut steps = problem.steps(SolverOptions { max_iterations: Some(1), ..Default::default() }).unwrap();
+    assert!(steps.next().unwrap().is_ok());
+    assert_eq!(steps.next().unwrap().err(), Some(SolveError::IterationLimit { iterations: 1 }));
+    assert!(steps.next().is_none());
+
+    let mut steps = Problem::maximize(1*var("x1") + 1*var("x2"),
+        Condition::each_le(
+            matrix! {
+                 1,  1
+                -1,  0
+            },
+            vec![
+                var("x1"),
+                var("x2"),
+            ],
+
+            /* less than or equals to */
+
+            vec![
+                 1,
+                -2,
+            ]
+        )
+    ).steps(SolverOptions::default()).unwrap();
+    assert_eq!(steps.by_ref().last().unwrap().err(), Some(SolveError::Infeasible));
+    assert_eq!(steps.phase(), Phase::One);
+
+    /* The constant of the objective function and the substituted bounds */
+    let (x, y) = (var("x"), var("y"));
+    let problem = Problem::maximize(3*&x + 2*&y + 100, Condition::from_constraints([
+        Constraint::le(1*&x + 1*&y, 4),
+        Constraint::ge(1*&x - 1*&y, -2),
+    ]))
+    .bound(x.clone(), 1, 3)
+    .free(y.clone());
+    /* x = 1 at the lower bound and y = 0 */
+    assert_eq!(Table::new(problem.clone()).unwrap().objective_value(), 103.);
+
+    let solution = problem.clone().solve().unwrap();
+    let last = problem.steps(SolverOptions::default()).unwrap()
+        .collect::<Result<Vec<_>, _>>().unwrap()
+        .pop().unwrap();
+    assert_eq!(last.objective_value, solution.optimal_value);
+    assert_eq!(last.variables, solution.variables);
+}
+
+#[test] fn test_render() {
+    let problem = Problem::maximize(3*var("x1") + 2*var("x2"),
+        Condition::each_le(
+            matrix! {
+                1, 1
+                1, 3
+            },
+            vec![
+                var("x1"),
+                var("x2"),
+            ],
+
+            /* less than or equals to */
+
+            vec![
+                4,
+                6,
+            ]
+        )
+    );
+    assert_eq!(problem.clone().steps(SolverOptions::default()).unwrap().to_markdown(4).unwrap(), "\
+|  |  | x1 ↓ | x2 | slack#1 | slack#2 |
+|---|---:|---:|---:|---:|---:|
+| slack#1 ← | 4 | **1** | 1 | 1 | 0 |
+| slack#2 | 6 | 1 | 3 | 0 | 1 |
+| #z | 0 | -3 | -2 | 0 | 0 |
+
+|  |  | x1 | x2 | slack#1 | slack#2 |
+|---|---:|---:|---:|---:|---:|
+| x1 | 4 | 1 | 1 | 1 | 0 |
+| slack#2 | 2 | 0 | 2 | -1 | 1 |
+| #z | 12 | 0 | 1 | 3 | 0 |
+");
+    assert_eq!(problem.steps(SolverOptions::default()).unwrap().to_latex(4).unwrap(), "\
+\\[
+\\begin{array}{c|r|rrrr}
+   &  & x_{1} \\downarrow & x_{2} & s_{1} & s_{2} \\\\
+  \\hline
+  s_{1} \\leftarrow & 4 & \\boxed{1} & 1 & 1 & 0 \\\\
+  s_{2} & 6 & 1 & 3 & 0 & 1 \\\\
+  \\hline
+  z & 0 & -3 & -2 & 0 & 0 \\\\
+\\end{array}
+\\]
+
+\\[
+\\begin{array}{c|r|rrrr}
+   &  & x_{1} & x_{2} & s_{1} & s_{2} \\\\
+  \\hline
+  x_{1} & 4 & 1 & 1 & 1 & 0 \\\\
+  s_{2} & 2 & 0 & 2 & -1 & 1 \\\\
+  \\hline
+  z & 12 & 0 & 1 & 3 & 0 \\\\
+\\end{array}
+\\]
+");
+
+    let table = Table::from_problem(Problem::minimize(1*var("cost_a") + 1*var("y"),
+        Condition::each_ge(
+            matrix! {
+                1, 1
+            },
+            vec![
+                var("cost_a"),
+                var("y"),
+            ],
+
+            /* greater than or equals to */
+
+            vec![
+                2,
+            ]
+        )
+    ));
+    assert_eq!(table.to_latex(Some(&Pivot { row:0, column:1, value:1. }), 4), "\
+\\begin{array}{c|r|rrrr}
+   &  & \\text{cost\\_a} & y \\downarrow & e_{1} & a_{1} \\\\
+  \\hline
+  a_{1} \\leftarrow & 2 & 1 & \\boxed{1} & -1 & 1 \\\\
+  \\hline
+  z & 0 & 1 & 1 & 0 & 0 \\\\
+\\end{array}
+");
+
+    /* Rounded values and a name with `\` */
+    let mut table = Table::new(Problem::maximize(1*var("a\\b"),
+        Condition::each_le(
+            matrix! {
+                3
+            },
+            vec![
+                var("a\\b"),
+            ],
+
+            /* less than or equals to */
+
+            vec![
+                1,
+            ]
+        )
+    )).unwrap();
+    table.pivot_on(0, 0, &SolverOptions::default()).unwrap();
+    assert_eq!(table.to_latex(None, 3), "\
+\\begin{array}{c|r|rr}
+   &  & \\text{a\\textbackslash{}b} & s_{1} \\\\
+  \\hline
+  \\text{a\\textbackslash{}b} & 0.333 & 1 & 0.333 \\\\
+  \\hline
+  z & 0.333 & 0 & 0.333 \\\\
+\\end{array}
+");
+}
+
+#[test] fn test_table_api() {
+    let problem = Problem::maximize(4*var("x1") + 5*var("x2"),
+        Condition::each_le(
+            matrix! {
+                2, 5
+                6, 4
+                3, 1
+            },
+            vec![
+                var("x1"),
+                var("x2"),
+            ],
+
+            /* less than or equals to */
+
+            vec![
+                20,
+                27,
+                12,
+            ]
+        )
+    );
+    let options = SolverOptions::default();
+
+    let mut table = Table::new(problem.clone()).unwrap();
+    assert_eq!(table.sense(), Sense::Maximize);
+    assert_eq!(table.variables(), [var("x1"), var("x2"), slack(1), slack(2), slack(3)]);
+    assert_eq!(table.basis().collect::<Vec<_>>(), [&slack(1), &slack(2), &slack(3)]);
+    assert_eq!(table.basic_values().collect::<Vec<_>>(), [20., 27., 12.]);
+    assert_eq!(table.criterions(), [-4., -5., 0., 0., 0.]);
+    assert_eq!(table.coefficients().column_size, 4);
+    assert_eq!(table.objective_value(), 0.);
+    assert!(!table.is_optimal(&options));
+
+    assert_eq!(table.pivot_on(3, 0, &options), Err(PivotError::RowOutOfBound { size: 3, index: 3 }));
+    assert_eq!(table.pivot_on(0, 5, &options), Err(PivotError::ColumnOutOfBound { size: 5, index: 5 }));
+    assert_eq!(table.pivot_on(0, 3, &options), Err(PivotError::ZeroElement { row: 0, column: 3 }));
+
+    /* Not the one of `pivot`, which is at row 0 */
+    assert_eq!(table.pivot_on(2, 0, &options), Ok(Pivot { row:2, column:0, value:3. }));
+    assert_eq!(table.basis().collect::<Vec<_>>(), [&slack(1), &slack(2), &var("x1")]);
+    assert_eq!(table.objective_value(), 16.);
+
+    while let Some(Pivot { row, column, .. }) = table.pivot(&options).unwrap() {
+        table.pivot_on(row, column, &options).unwrap();
+    }
+    assert!(table.is_optimal(&options));
+    assert_eq!(table.pivot(&options), Ok(None));
+    assert_eq!(table.objective_value(), problem.solve().unwrap().optimal_value);
+
+    /* An element within `pivot_tolerance` is 0 */
+    let mut table = Table::new(Problem::maximize(1*var("x1") + 1*var("x2"),
+        Condition::each_le(matrix! {1e-12, 1}, vec![var("x1"), var("x2")], vec![1])
+    )).unwrap();
+    assert_eq!(table.pivot_on(0, 0, &options), Err(PivotError::ZeroElement { row: 0, column: 0 }));
+
+    let table = Table::new(Problem::maximize(1*var("x2"),
+        Condition::each_le(matrix! {1, -1}, vec![var("x1"), var("x2")], vec![1])
+    )).unwrap();
+    assert_eq!(table.pivot(&options), Err(PivotError::Unbounded { column: 1 }));
+
+    assert_eq!(
+        Table::new(Problem::maximize(1*var("x1"), Condition::each_le(matrix! {1, 1}, vec![var("x1")], vec![1]))).err(),
+        Some(SolveError::DimensionMismatch { name: "x", expected: 2, found: 1 })
     );
+}
+
+#[test] fn test_reoptimize() {
+    let (x, y) = (var("x"), var("y"));
+    let problem = || Problem::maximize(3*&x + 2*&y, Condition::from_constraints([
+        Constraint::le(1*&x + 1*&y, 4),
+        Constraint::le(1*&x + 3*&y, 6),
+    ]));
+    let options = SolverOptions::default();
+
+    /* From the initial table, same as solving */
+    let mut table = Table::new(problem()).unwrap();
+    assert_eq!(table.reoptimize(&options).unwrap(), problem().solve().unwrap());
+    assert_eq!(table.objective_value(), 12.);
+
+    /* A branch `x ≤ 3` from the optimal table is reoptimized by a pivot operation of dual simplex method */
+    let mut branch = table.clone();
+    branch.tighten_bound(x.clone(), Range { lower: -Scalor::INFINITY, upper: 3. }).unwrap();
+    assert_eq!(branch.basic_values().filter(|value| *value < 0.).count(), 1);
+    let solution = branch.reoptimize(&SolverOptions { max_iterations: Some(1), ..options }).unwrap();
+    let expected = problem().bound(x.clone(), 0, 3).solve().unwrap();
+    assert_eq!(solution.optimal_value, expected.optimal_value);
+    assert_eq!(solution.variables, expected.variables);
+    assert_eq!(solution.duals.len(), 2);
 
+    /* `≥` and `=` rows */
+    let mut ge = table.clone();
+    ge.add_constraint(Constraint::ge(1*&y, 1.5)).unwrap();
+    let solution = ge.reoptimize(&options).unwrap();
+    let expected = problem().bound(y.clone(), 1.5, Scalor::INFINITY).solve().unwrap();
+    assert_eq!((solution.optimal_value, solution.variables), (expected.optimal_value, expected.variables));
+
+    let mut eq = table.clone();
+    eq.add_constraint(Constraint::eq(1*&x - 1*&y, 1)).unwrap();
+    let solution = eq.reoptimize(&options).unwrap();
+    assert!((solution.optimal_value - 9.25).abs() < 1e-9);
+    assert!((solution.variables[&x] - 2.25).abs() < 1e-9);
+    assert!((solution.variables[&y] - 1.25).abs() < 1e-9);
+
+    /* Bounds substituted by `Table::new` and the constant of the objective function */
+    let bounded = || Problem::minimize(1*&x + 1*&y + 10, Condition::from_constraints([
+        Constraint::ge(1*&x + 1*&y, 3),
+    ]))
+    .bound(x.clone(), 1, 5)
+    .bound(y.clone(), -Scalor::INFINITY, 4);
+    let mut table = Table::new(bounded()).unwrap();
+    table.reoptimize(&options).unwrap();
+    table.tighten_bound(x.clone(), Range { lower: 2.5, upper: Scalor::INFINITY }).unwrap();
+    table.tighten_bound(y.clone(), Range { lower: 1., upper: 3. }).unwrap();
+    let solution = table.reoptimize(&options).unwrap();
+    let expected = bounded().bound(x.clone(), 2.5, 5).bound(y.clone(), 1, 3).solve().unwrap();
+    assert!((solution.optimal_value - expected.optimal_value).abs() < 1e-9);
+    assert_eq!(solution.optimal_value, 13.5);
+    assert!((solution.variables[&x] - 2.5).abs() < 1e-9);
+    assert!((solution.variables[&y] - 1.).abs() < 1e-9);
+
+    /* Errors */
+    let mut infeasible = table.clone();
+    infeasible.tighten_bound(x.clone(), Range { lower: -Scalor::INFINITY, upper: 2. }).unwrap();
+    assert_eq!(infeasible.reoptimize(&options), Err(SolveError::Infeasible));
+
+    let mut not_optimal = Table::new(problem()).unwrap();
+    not_optimal.tighten_bound(x.clone(), Range { lower: 1., upper: Scalor::INFINITY }).unwrap();
+    assert_eq!(not_optimal.reoptimize(&options), Err(SolveError::NotDualFeasible));
+
+    assert_eq!(
+        Table::new(problem()).unwrap().add_constraint(Constraint::le(1*var("z"), 1)),
+        Err(SolveError::UnknownVariable { variable: var("z") })
+    );
 }